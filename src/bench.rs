@@ -0,0 +1,148 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::files;
+use crate::parameters::Parameters;
+use crate::solution::{Part, Solution};
+
+/// How long each stage of a solution took, one entry per run.
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// Parses the input and solves the given parts `runs` times, timing every stage on its own.
+pub fn measure<S: Solution>(input: &str, parts: &[Part], parameters: &Parameters, runs: usize) -> Result<Timings, Error> {
+    let input = files::normalise(input)?;
+    let mut timings = Timings::default();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(&input)).map_err(|error| error.locate(&input))?;
+        timings.parse.push(start.elapsed());
+        for part in parts {
+            let start = Instant::now();
+            match part {
+                Part::One => black_box(S::part1(&parsed, parameters)?),
+                Part::Two => black_box(S::part2(&parsed, parameters)?),
+            };
+            let elapsed = start.elapsed();
+            match part {
+                Part::One => timings.part1.push(elapsed),
+                Part::Two => timings.part2.push(elapsed),
+            }
+        }
+    }
+    Ok(timings)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Statistics {
+    pub fn of(durations: &[Duration]) -> Option<Self> {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() {
+            0 => return None,
+            length if length % 2 == 0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+        let mean = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+        Some(Statistics { min: sorted[0], median, mean, max: sorted[sorted.len() - 1] })
+    }
+}
+
+/// The statistics of one stage (`parse`, `part1` or `part2`) of a day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: String,
+    pub statistics: Statistics,
+}
+
+pub fn measurements(day: u8, timings: &Timings) -> Vec<Measurement> {
+    [("parse", &timings.parse), ("part1", &timings.part1), ("part2", &timings.part2)].into_iter()
+        .filter_map(|(stage, durations)| Statistics::of(durations)
+            .map(|statistics| Measurement { day, stage: stage.to_string(), statistics }))
+        .collect()
+}
+
+/// Writes the measurements as whitespace separated columns in nanoseconds, which [`load`]
+/// reads back to compare a later run with.
+pub fn save(file: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let mut text = String::from("# day stage min median mean max (nanoseconds)\n");
+    for m in measurements {
+        let s = m.statistics;
+        writeln!(text, "{} {} {} {} {} {}", m.day, m.stage,
+                 s.min.as_nanos(), s.median.as_nanos(), s.mean.as_nanos(), s.max.as_nanos()).unwrap();
+    }
+    fs::write(file, text).map_err(|error| format!("Could not write {}: {}", file.display(), error))
+}
+
+pub fn load(file: &Path) -> Result<Vec<Measurement>, String> {
+    let text = fs::read_to_string(file).map_err(|error| format!("Could not read {}: {}", file.display(), error))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let invalid = || format!("{} line {}: expected <day> <stage> <min> <median> <mean> <max>", file.display(), number + 1);
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.len() != 6 {
+                return Err(invalid());
+            }
+            let nanos = |column: &str| column.parse::<u64>().map(Duration::from_nanos).map_err(|_| invalid());
+            Ok(Measurement {
+                day: columns[0].parse().map_err(|_| invalid())?,
+                stage: columns[1].to_string(),
+                statistics: Statistics {
+                    min: nanos(columns[2])?,
+                    median: nanos(columns[3])?,
+                    mean: nanos(columns[4])?,
+                    max: nanos(columns[5])?,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Relative change of the median compared to the previous measurement of the same stage.
+pub fn change(measurement: &Measurement, previous: &[Measurement]) -> Option<f64> {
+    let before = previous.iter()
+        .find(|p| p.day == measurement.day && p.stage == measurement.stage)?
+        .statistics.median.as_secs_f64();
+    let now = measurement.statistics.median.as_secs_f64();
+    (before > 0.0).then(|| (now - before) / before)
+}
+
+pub fn print_header(comparing: bool) {
+    print!("Day  Stage  {:>12} {:>12} {:>12} {:>12}", "min", "median", "mean", "max");
+    if comparing {
+        print!("  {:>8}", "change");
+    }
+    println!();
+}
+
+pub fn print_measurement(measurement: &Measurement, change: Option<f64>, threshold: f64) {
+    let s = measurement.statistics;
+    print!("{:>3}  {:<5}  {:>12} {:>12} {:>12} {:>12}", format!("{:02}", measurement.day), measurement.stage,
+           format!("{:.2?}", s.min), format!("{:.2?}", s.median), format!("{:.2?}", s.mean), format!("{:.2?}", s.max));
+    if let Some(change) = change {
+        print!("  {:>+7.1}%", change * 100.0);
+        if change > threshold {
+            print!("  REGRESSION");
+        }
+    }
+    println!();
+}
@@ -0,0 +1,522 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use advent_of_code_2022::parameters::{parse_day, Override};
+use advent_of_code_2022::render::ImageFormat;
+use advent_of_code_2022::runner::{self, InputSource};
+use advent_of_code_2022::solution::Part;
+use advent_of_code_2022::trace::Level;
+
+pub const USAGE: &str = "\
+Usage: advent_of_code_2022 run <day> [--part 1|2] [<input>] [<parameters>] [<limits>] [<trace>] [<output>]
+                               [<render>]
+       advent_of_code_2022 run <first>-<last> [--part 1|2] [--example|--profile <name>] [<parameters>] [<limits>]
+                               [<trace>] [<output>] [<render>]
+       advent_of_code_2022 run --all [--part 1|2] [--example|--profile <name>] [<parameters>] [<limits>] [<trace>]
+                               [<output>] [<render>]
+       advent_of_code_2022 run <days>|--all --all-profiles [--part 1|2] [<parameters>] [<limits>]
+       advent_of_code_2022 bench <days> [--runs <n>] [--save <file>] [--compare <file>] [--threshold <percent>]
+                                 [--part 1|2] [--example|--profile <name>] [<parameters>] [<limits>]
+       advent_of_code_2022 params [<day>]
+       advent_of_code_2022 generate <day> [--size <n>] [--seed <n>] [--output <file>]
+       advent_of_code_2022 fetch <day> [--force] [--config <file>]
+       advent_of_code_2022 submit <day> 1|2 [--answer <answer>] [--config <file>]
+       advent_of_code_2022 new <day>
+       advent_of_code_2022 watch <day> [--part 1|2] [--example|--input <file>|--profile <name>]
+                                 [--interval <ms>] [<parameters>] [<limits>] [<trace>]
+
+Output:
+  --format text|json|csv   print the answers as text (default), or as JSON or CSV with
+                           the fields day, part, input, answer, elapsed_ns and error
+
+Render (the solved parts of days 8, 9, 10, 12 and 14, after the answers as text):
+  --render ansi|ppm|png   print the picture of where a part ends in colour (ansi), or write
+                          it to dayNN-partN.ppm or dayNN-partN.png
+  --frames <dir>          write the pictures of every state on the way as numbered images
+                          into <dir>/dayNN-partN instead
+
+Input (defaults to resources/dayNN.txt):
+  --example         use resources/dayNN-test.txt
+  --input <file>    read the puzzle input from <file>, or from stdin if <file> is '-'
+  --inline <text>   use <text> as puzzle input
+  --profile <name>  use resources/<name>/dayNN.txt, the input of another player
+  --all-profiles    solve resources/dayNN.txt and the input of every profile and
+                    print the answers side by side
+
+Parameters (default to the values of the puzzle, or of the example for *-test.txt inputs):
+  --param [dayNN.]<name>=<value>   set a puzzle parameter, can be repeated
+  --config <file>                  read parameters from the [dayNN] sections of <file>
+                                   instead of aoc.ini
+
+Limits:
+  --timeout <seconds>   give up on a part after <seconds>, 0 for no limit (default 60),
+                        a benchmark gives up on a day after <seconds> per run
+
+Trace (printed to stderr):
+  --trace info|debug|trace   trace the days and their answers (info), parsing, the parts and
+                             their intermediate results (debug), or also every step of the
+                             simulations of days 5, 11 and 14 (trace)
+  --trace-file <file>        write the trace to <file> as JSON lines with the fields time_us,
+                             level, kind, span, name and fields
+
+Benchmark:
+  --runs <n>               number of runs per day (default 10)
+  --save <file>            write the results to <file>
+  --compare <file>         compare the medians with results saved earlier
+  --threshold <percent>    slow down flagged as regression (default 10)
+
+Generate (prints a random input, the same for the same size and seed):
+  --size <n>       size of the input (default 100): the number of elves (1), rounds (2),
+                   rucksacks (3), pairs (4), moves (5), characters (6), directories (7),
+                   motions (9), instructions (10), items (11), packet pairs (13),
+                   rock paths (14) or sensors (15), or the width of the map (8, 12)
+  --seed <n>       seed of the random numbers (default 1)
+  --output <file>  write the input to <file> instead of printing it
+
+Watch (solves the day again whenever its input changes, and rebuilds and restarts whenever
+src/dayNN.rs changes, until interrupted):
+  --interval <ms>   time between two checks of the input files and the source (default 500)
+  by default the example and the puzzle input are both watched and solved
+
+Fetch and submit (use the session token of $AOC_SESSION, or else of the [client] section of the config):
+  --force            download the input even if resources/dayNN.txt exists
+  --answer <answer>  submit <answer> instead of the answer to resources/dayNN.txt
+  --config <file>    read the [client] section of <file> instead of aoc.ini";
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Selection {
+    All,
+    Days(RangeInclusive<u8>),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// How the pictures of the days are shown, see [`RunOptions::render`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rendering {
+    Ansi,
+    Image(ImageFormat),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub part: Option<Part>,
+    pub source: InputSource,
+    pub overrides: Vec<Override>,
+    pub config: Option<PathBuf>,
+    pub format: Format,
+    /// Solve the puzzle input of every profile rather than one source.
+    pub all_profiles: bool,
+    /// How long a part may take before it is given up on, `None` for no limit.
+    pub timeout: Option<Duration>,
+    pub trace: Option<Level>,
+    /// Where the trace is written as JSON lines, stderr gets it as text otherwise.
+    pub trace_file: Option<PathBuf>,
+    pub render: Option<Rendering>,
+    /// Where every picture of a part goes as a frame, only the last one is drawn otherwise.
+    pub frames: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub run: RunOptions,
+    pub runs: usize,
+    pub save: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    /// Relative slow down of the median that counts as a regression, e.g. `0.1` for 10%.
+    pub threshold: f64,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct GenerateOptions {
+    pub day: u8,
+    pub size: usize,
+    pub seed: u64,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct FetchOptions {
+    pub day: u8,
+    pub force: bool,
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct SubmitOptions {
+    pub day: u8,
+    pub part: Part,
+    /// The answer to submit, solved from the puzzle input if there is none.
+    pub answer: Option<String>,
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct WatchOptions {
+    pub run: RunOptions,
+    pub interval: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    /// Lists the puzzle parameters of one or every day.
+    Params(Option<u8>),
+    Generate(GenerateOptions),
+    Fetch(FetchOptions),
+    Submit(SubmitOptions),
+    /// Creates the files of a new day.
+    New(u8),
+    Watch(WatchOptions),
+    Help,
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, String> where I: IntoIterator<Item=String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None | Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("bench") => parse_bench(args),
+        Some("params") => parse_params(args),
+        Some("generate") => parse_generate(args).map(Command::Generate),
+        Some("fetch") => parse_fetch(args).map(Command::Fetch),
+        Some("submit") => parse_submit(args).map(Command::Submit),
+        Some("new") => parse_new(args),
+        Some("watch") => parse_watch(args).map(Command::Watch),
+        Some(other) => Err(format!("Unknown command '{}'", other)),
+    }
+}
+
+fn parse_run<I>(mut args: I) -> Result<RunOptions, String> where I: Iterator<Item=String> {
+    let mut selection: Option<Selection> = None;
+    let mut part: Option<Part> = None;
+    let mut source: Option<InputSource> = None;
+    let mut overrides: Vec<Override> = Vec::new();
+    let mut config: Option<PathBuf> = None;
+    let mut format = Format::Text;
+    let mut all_profiles = false;
+    let mut timeout = Some(runner::DEFAULT_TIMEOUT);
+    let mut trace: Option<Level> = None;
+    let mut trace_file: Option<PathBuf> = None;
+    let mut render: Option<Rendering> = None;
+    let mut frames: Option<PathBuf> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => selection = Some(Selection::All),
+            "--all-profiles" => all_profiles = true,
+            "--timeout" => {
+                let value = args.next().ok_or("Missing value for --timeout")?;
+                let seconds = value.parse::<f64>().ok().filter(|seconds| *seconds >= 0.0 && seconds.is_finite())
+                    .ok_or_else(|| format!("Timeout must be a number of seconds, got '{}'", value))?;
+                timeout = Some(Duration::from_secs_f64(seconds)).filter(|timeout| !timeout.is_zero());
+            }
+            "--trace" => {
+                let value = args.next().ok_or("Missing value for --trace")?;
+                trace = Some(Level::parse(&value)
+                    .ok_or_else(|| format!("Trace level must be info, debug or trace, got '{}'", value))?);
+            }
+            "--trace-file" => trace_file = Some(PathBuf::from(args.next().ok_or("Missing value for --trace-file")?)),
+            "--part" => {
+                let value = args.next().ok_or("Missing value for --part")?;
+                part = Some(parse_part(&value)?);
+            }
+            "--example" => set_source(&mut source, InputSource::Example)?,
+            "--input" => {
+                let value = args.next().ok_or("Missing value for --input")?;
+                let input = match value.as_str() {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::File(PathBuf::from(path)),
+                };
+                set_source(&mut source, input)?
+            }
+            "--inline" => {
+                let value = args.next().ok_or("Missing value for --inline")?;
+                set_source(&mut source, InputSource::Inline(value))?
+            }
+            "--profile" => {
+                let value = args.next().ok_or("Missing value for --profile")?;
+                if value.is_empty() || value.contains(['/', '\\']) || value.starts_with('.') {
+                    return Err(format!("'{}' is not a valid profile name", value));
+                }
+                set_source(&mut source, InputSource::Profile(value))?
+            }
+            "--param" => {
+                let value = args.next().ok_or("Missing value for --param")?;
+                overrides.push(Override::parse(&value)?);
+            }
+            "--config" => {
+                let value = args.next().ok_or("Missing value for --config")?;
+                config = Some(PathBuf::from(value));
+            }
+            "--format" => {
+                let value = args.next().ok_or("Missing value for --format")?;
+                format = parse_format(&value)?;
+            }
+            "--render" => {
+                let value = args.next().ok_or("Missing value for --render")?;
+                render = Some(parse_rendering(&value)?);
+            }
+            "--frames" => frames = Some(PathBuf::from(args.next().ok_or("Missing value for --frames")?)),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            days => {
+                if selection.is_some() {
+                    return Err(format!("Unexpected argument '{}'", days));
+                }
+                selection = Some(Selection::Days(parse_days(days)?));
+            }
+        }
+    }
+
+    let selection = selection.ok_or("Select a day, a range of days or --all")?;
+    if all_profiles && source.is_some() {
+        return Err("--all-profiles solves the puzzle inputs, it can't be used with another input".to_string());
+    }
+    if all_profiles && format != Format::Text {
+        return Err("--all-profiles prints a table, it can't be used with --format".to_string());
+    }
+    if trace_file.is_some() && trace.is_none() {
+        return Err("--trace-file needs a --trace level".to_string());
+    }
+    if render.is_some() && (format != Format::Text || all_profiles) {
+        return Err("--render draws below the answers as text, it can't be used with --format or --all-profiles".to_string());
+    }
+    if frames.is_some() && !matches!(render, Some(Rendering::Image(_))) {
+        return Err("--frames needs --render ppm or png".to_string());
+    }
+    let source = source.unwrap_or(InputSource::Puzzle);
+    if source.is_explicit() && !selects_single_day(&selection) {
+        return Err("--input and --inline can only be used with a single day".to_string());
+    }
+    Ok(RunOptions { selection, part, source, overrides, config, format, all_profiles, timeout, trace, trace_file, render, frames })
+}
+
+/// Takes the options only a benchmark has and leaves the rest to [`parse_run`].
+fn parse_bench<I>(mut args: I) -> Result<Command, String> where I: Iterator<Item=String> {
+    let mut runs = 10;
+    let mut save: Option<PathBuf> = None;
+    let mut compare: Option<PathBuf> = None;
+    let mut threshold = 10.0;
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let value = args.next().ok_or("Missing value for --runs")?;
+                runs = value.parse::<usize>().ok().filter(|runs| *runs > 0)
+                    .ok_or_else(|| format!("Number of runs must be a positive number, got '{}'", value))?;
+            }
+            "--save" => save = Some(PathBuf::from(args.next().ok_or("Missing value for --save")?)),
+            "--compare" => compare = Some(PathBuf::from(args.next().ok_or("Missing value for --compare")?)),
+            "--threshold" => {
+                let value = args.next().ok_or("Missing value for --threshold")?;
+                threshold = value.trim_end_matches('%').parse::<f64>().ok().filter(|threshold| *threshold >= 0.0)
+                    .ok_or_else(|| format!("Threshold must be a percentage, got '{}'", value))?;
+            }
+            "--input" | "--inline" | "--profile" | "--part" | "--param" | "--config" | "--format" | "--trace" | "--trace-file" | "--timeout"
+            | "--render" | "--frames" => {
+                rest.push(arg);
+                rest.extend(args.next());
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let run = parse_run(rest.into_iter())?;
+    if run.format != Format::Text {
+        return Err("--format can only be used with run".to_string());
+    }
+    if run.all_profiles {
+        return Err("--all-profiles can only be used with run".to_string());
+    }
+    if run.trace.is_some() {
+        return Err("--trace can only be used with run and watch".to_string());
+    }
+    if run.render.is_some() {
+        return Err("--render can only be used with run".to_string());
+    }
+    Ok(Command::Bench(BenchOptions { run, runs, save, compare, threshold: threshold / 100.0 }))
+}
+
+fn parse_params<I>(mut args: I) -> Result<Command, String> where I: Iterator<Item=String> {
+    let day = args.next().as_deref().map(parse_day).transpose()?;
+    match args.next() {
+        Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+        None => Ok(Command::Params(day)),
+    }
+}
+
+fn parse_generate<I>(mut args: I) -> Result<GenerateOptions, String> where I: Iterator<Item=String> {
+    let mut day: Option<u8> = None;
+    let mut size = 100;
+    let mut seed = 1;
+    let mut output: Option<PathBuf> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let value = args.next().ok_or("Missing value for --size")?;
+                size = value.parse::<usize>().ok().filter(|size| *size > 0)
+                    .ok_or_else(|| format!("Size must be a positive number, got '{}'", value))?;
+            }
+            "--seed" => {
+                let value = args.next().ok_or("Missing value for --seed")?;
+                seed = value.parse::<u64>().map_err(|_| format!("Seed must be a number, got '{}'", value))?;
+            }
+            "--output" => output = Some(PathBuf::from(args.next().ok_or("Missing value for --output")?)),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            value if day.is_none() => day = Some(parse_day(value)?),
+            value => return Err(format!("Unexpected argument '{}'", value)),
+        }
+    }
+
+    let day = day.ok_or("Select the day to generate an input for")?;
+    Ok(GenerateOptions { day, size, seed, output })
+}
+
+/// Takes the interval and leaves the rest to [`parse_run`].
+fn parse_watch<I>(mut args: I) -> Result<WatchOptions, String> where I: Iterator<Item=String> {
+    let mut interval = Duration::from_millis(500);
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--interval" => {
+                let value = args.next().ok_or("Missing value for --interval")?;
+                interval = value.parse::<u64>().ok().filter(|milliseconds| *milliseconds > 0).map(Duration::from_millis)
+                    .ok_or_else(|| format!("Interval must be a positive number of milliseconds, got '{}'", value))?;
+            }
+            "--input" | "--inline" | "--profile" | "--part" | "--param" | "--config" | "--format" | "--trace" | "--trace-file"
+            | "--render" | "--frames" => {
+                rest.push(arg);
+                rest.extend(args.next());
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let run = parse_run(rest.into_iter())?;
+    if !selects_single_day(&run.selection) {
+        return Err("watch can only be used with a single day".to_string());
+    }
+    if matches!(run.source, InputSource::Stdin | InputSource::Inline(_)) {
+        return Err("watch needs an input file, not --input - or --inline".to_string());
+    }
+    if run.format != Format::Text || run.all_profiles || run.render.is_some() {
+        return Err("--format, --all-profiles and --render can only be used with run".to_string());
+    }
+    Ok(WatchOptions { run, interval })
+}
+
+fn parse_new<I>(mut args: I) -> Result<Command, String> where I: Iterator<Item=String> {
+    let day = args.next().ok_or("Select the day to create")?;
+    let day = parse_day(&day)?;
+    match args.next() {
+        Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+        None => Ok(Command::New(day)),
+    }
+}
+
+fn parse_fetch<I>(mut args: I) -> Result<FetchOptions, String> where I: Iterator<Item=String> {
+    let mut day: Option<u8> = None;
+    let mut force = false;
+    let mut config: Option<PathBuf> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--force" => force = true,
+            "--config" => config = Some(PathBuf::from(args.next().ok_or("Missing value for --config")?)),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            value if day.is_none() => day = Some(parse_day(value)?),
+            value => return Err(format!("Unexpected argument '{}'", value)),
+        }
+    }
+
+    let day = day.ok_or("Select the day to fetch the input of")?;
+    Ok(FetchOptions { day, force, config })
+}
+
+fn parse_submit<I>(mut args: I) -> Result<SubmitOptions, String> where I: Iterator<Item=String> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<Part> = None;
+    let mut answer: Option<String> = None;
+    let mut config: Option<PathBuf> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answer" => answer = Some(args.next().ok_or("Missing value for --answer")?),
+            "--config" => config = Some(PathBuf::from(args.next().ok_or("Missing value for --config")?)),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            value if day.is_none() => day = Some(parse_day(value)?),
+            value if part.is_none() => part = Some(parse_part(value)?),
+            value => return Err(format!("Unexpected argument '{}'", value)),
+        }
+    }
+
+    let day = day.ok_or("Select the day to submit an answer for")?;
+    let part = part.ok_or("Select the part to submit an answer for")?;
+    Ok(SubmitOptions { day, part, answer, config })
+}
+
+fn set_source(current: &mut Option<InputSource>, new: InputSource) -> Result<(), String> {
+    match current.replace(new) {
+        Some(_) => Err("Only one of --example, --input, --inline and --profile can be given".to_string()),
+        None => Ok(()),
+    }
+}
+
+fn selects_single_day(selection: &Selection) -> bool {
+    match selection {
+        Selection::All => false,
+        Selection::Days(days) => days.start() == days.end(),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(format!("Format must be text, json or csv, got '{}'", value)),
+    }
+}
+
+fn parse_rendering(value: &str) -> Result<Rendering, String> {
+    match value {
+        "ansi" => Ok(Rendering::Ansi),
+        _ => ImageFormat::parse(value).map(Rendering::Image)
+            .ok_or_else(|| format!("Rendering must be ansi, ppm or png, got '{}'", value)),
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("Part must be 1 or 2, got '{}'", value)),
+    }
+}
+
+fn parse_days(value: &str) -> Result<RangeInclusive<u8>, String> {
+    match value.split_once('-') {
+        Some((first, last)) => {
+            let (first, last) = (parse_day(first)?, parse_day(last)?);
+            if first > last {
+                return Err(format!("Empty range of days '{}'", value));
+            }
+            Ok(first..=last)
+        }
+        None => {
+            let day = parse_day(value)?;
+            Ok(day..=day)
+        }
+    }
+}
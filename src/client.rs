@@ -0,0 +1,309 @@
+//! Downloads puzzle inputs and submits answers to the Advent of Code website, or to any
+//! server that answers the same requests.
+//!
+//! The session token, the value of the 'session' cookie of the website, is a credential.
+//! It is best kept in the environment variable `AOC_SESSION`, and otherwise in the config
+//! file, which `.gitignore` keeps out of the repository as long as it is `aoc.ini`. The
+//! rest of the client is configured in the `[client]` section of the config file:
+//!
+//! ```text
+//! [client]
+//! # only used if AOC_SESSION is not set
+//! session = 53616c7465645f5f...
+//! # optional, the server and the seconds to wait between two requests
+//! url = https://adventofcode.com
+//! interval = 5
+//! ```
+//!
+//! Every answer sent is remembered in `submissions.txt` next to the inputs, so that the
+//! same wrong answer is never sent twice and numbers outside of the known bounds are
+//! rejected without asking the server.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::solution::Part;
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+pub const YEAR: u16 = 2022;
+
+/// The environment variable holding the session token, which wins over the config.
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The file remembering the submitted answers.
+const SUBMISSIONS_FILE: &str = "submissions.txt";
+
+/// The file holding the time of the last request, so that the rate limit holds across runs.
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    CORRECT,
+    WRONG,
+    TOO_HIGH,
+    TOO_LOW,
+    /// The server refused to check the answer that early after a wrong one, with the
+    /// reason it gave.
+    TOO_SOON(String),
+    /// The part has been solved before, but the correct answer is unknown.
+    ALREADY_SOLVED,
+}
+
+impl Verdict {
+    /// Reads the verdict from the page the server answered with.
+    fn of(page: &str) -> Result<Self, String> {
+        if page.contains("That's the right answer") {
+            Ok(Verdict::CORRECT)
+        } else if page.contains("That's not the right answer") {
+            Ok(if page.contains("too high") {
+                Verdict::TOO_HIGH
+            } else if page.contains("too low") {
+                Verdict::TOO_LOW
+            } else {
+                Verdict::WRONG
+            })
+        } else if page.contains("You gave an answer too recently") {
+            let reason = page.split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| format!("{} left to wait", wait))
+                .unwrap_or_else(|| "answered too recently".to_string());
+            Ok(Verdict::TOO_SOON(reason))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::ALREADY_SOLVED)
+        } else {
+            Err("Could not find a verdict in the answer of the server".to_string())
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::CORRECT => "CORRECT",
+            Verdict::WRONG => "WRONG",
+            Verdict::TOO_HIGH => "TOO_HIGH",
+            Verdict::TOO_LOW => "TOO_LOW",
+            Verdict::TOO_SOON(_) => "TOO_SOON",
+            Verdict::ALREADY_SOLVED => "ALREADY_SOLVED",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "CORRECT" => Some(Verdict::CORRECT),
+            "WRONG" => Some(Verdict::WRONG),
+            "TOO_HIGH" => Some(Verdict::TOO_HIGH),
+            "TOO_LOW" => Some(Verdict::TOO_LOW),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::CORRECT => write!(f, "That's the right answer"),
+            Verdict::WRONG => write!(f, "That's not the right answer"),
+            Verdict::TOO_HIGH => write!(f, "That's not the right answer, it is too high"),
+            Verdict::TOO_LOW => write!(f, "That's not the right answer, it is too low"),
+            Verdict::TOO_SOON(reason) => write!(f, "Answered too recently, {}", reason),
+            Verdict::ALREADY_SOLVED => write!(f, "The part has already been solved"),
+        }
+    }
+}
+
+/// The result of a submission: the verdict and whether the server was asked for it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// `false` if the verdict is known from an earlier submission.
+    pub sent: bool,
+}
+
+/// An answer submitted earlier.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Submitted {
+    day: u8,
+    part: Part,
+    verdict: Verdict,
+    answer: String,
+}
+
+pub struct Client {
+    url: String,
+    session: String,
+    interval: Duration,
+    /// Where the inputs are cached and the submissions remembered, usually `resources`.
+    directory: PathBuf,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(url: &str, session: &str, interval: Duration, directory: &Path) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Client {
+            url: url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            interval,
+            directory: directory.to_path_buf(),
+            agent,
+        }
+    }
+
+    /// A client for the server of the `[client]` section of the config, with the session
+    /// token of [`SESSION_VARIABLE`] or else of the config.
+    pub fn from_config(config: &Config, directory: &Path) -> Result<Self, String> {
+        Client::from_config_with_session(config, directory, env::var(SESSION_VARIABLE).ok().as_deref())
+    }
+
+    /// Like [`Client::from_config`], with `session` in place of the value of the variable.
+    pub fn from_config_with_session(config: &Config, directory: &Path, session: Option<&str>) -> Result<Self, String> {
+        let session = session.map(str::trim).filter(|session| !session.is_empty())
+            .or_else(|| config.get("client", "session"))
+            .ok_or_else(|| format!("There is no session token, set {} or 'session' in the [client] section of the config", SESSION_VARIABLE))?;
+        let url = config.get("client", "url").unwrap_or(DEFAULT_URL);
+        let interval = match config.get("client", "interval") {
+            None => DEFAULT_INTERVAL,
+            Some(seconds) => seconds.parse::<f64>().ok().filter(|seconds| *seconds >= 0.0)
+                .map(Duration::from_secs_f64)
+                .ok_or_else(|| format!("Interval in [client] must be a number of seconds, got '{}'", seconds))?,
+        };
+        Ok(Client::new(url, session, interval, directory))
+    }
+
+    pub fn input_file(&self, day: u8) -> PathBuf {
+        self.directory.join(format!("day{:02}.txt", day))
+    }
+
+    /// Downloads the puzzle input into the input file of the day, unless it is there
+    /// already. Returns the input file.
+    pub fn fetch(&self, day: u8, force: bool) -> Result<PathBuf, String> {
+        let file = self.input_file(day);
+        if file.exists() && !force {
+            return Ok(file);
+        }
+        self.wait_for_turn()?;
+        let url = format!("{}/{}/day/{}/input", self.url, YEAR, day);
+        let response = self.agent.get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|error| request_failed(&url, error))?;
+        let input = response.into_string().map_err(|error| format!("Could not read the input from {}: {}", url, error))?;
+        fs::create_dir_all(&self.directory).and_then(|_| fs::write(&file, input))
+            .map_err(|error| format!("Could not write {}: {}", file.display(), error))?;
+        Ok(file)
+    }
+
+    /// Sends the answer unless its verdict can be told from the earlier submissions.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Submission, String> {
+        let submitted = self.submitted()?;
+        if let Some(verdict) = known_verdict(&submitted, day, part, answer) {
+            return Ok(Submission { verdict, sent: false });
+        }
+
+        self.wait_for_turn()?;
+        let url = format!("{}/{}/day/{}/answer", self.url, YEAR, day);
+        let level = part.number().to_string();
+        let response = self.agent.post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|error| request_failed(&url, error))?;
+        let page = response.into_string().map_err(|error| format!("Could not read the answer of {}: {}", url, error))?;
+        let verdict = Verdict::of(&page)?;
+
+        if !matches!(verdict, Verdict::TOO_SOON(_) | Verdict::ALREADY_SOLVED) {
+            self.remember(&Submitted { day, part, verdict: verdict.clone(), answer: answer.to_string() })?;
+        }
+        Ok(Submission { verdict, sent: true })
+    }
+
+    /// Sleeps until the interval since the last request of any run has passed.
+    fn wait_for_turn(&self) -> Result<(), String> {
+        let file = self.directory.join(LAST_REQUEST_FILE);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let last = fs::read_to_string(&file).ok()
+            .and_then(|text| text.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(elapsed) = last.and_then(|last| now.checked_sub(last)) {
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        fs::create_dir_all(&self.directory).and_then(|_| fs::write(&file, now.as_millis().to_string()))
+            .map_err(|error| format!("Could not write {}: {}", file.display(), error))
+    }
+
+    fn submitted(&self) -> Result<Vec<Submitted>, String> {
+        let file = self.directory.join(SUBMISSIONS_FILE);
+        let text = match fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(format!("Could not read {}: {}", file.display(), error)),
+        };
+        text.lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| parse_submitted(line).ok_or_else(|| format!("{}: invalid line '{}'", file.display(), line)))
+            .collect()
+    }
+
+    fn remember(&self, submitted: &Submitted) -> Result<(), String> {
+        let file = self.directory.join(SUBMISSIONS_FILE);
+        let mut text = fs::read_to_string(&file).unwrap_or_else(|_| "# day part verdict answer\n".to_string());
+        text.push_str(&format!("{} {} {} {}\n", submitted.day, submitted.part.number(), submitted.verdict.name(),
+                               submitted.answer.replace('\n', "\\n")));
+        fs::write(&file, text).map_err(|error| format!("Could not write {}: {}", file.display(), error))
+    }
+}
+
+fn request_failed(url: &str, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(400, _) => format!("{} refused the request, is the session token still valid?", url),
+        ureq::Error::Status(404, _) => format!("{} does not exist, the puzzle may not be unlocked yet", url),
+        ureq::Error::Status(code, _) => format!("{} answered with status {}", url, code),
+        ureq::Error::Transport(transport) => format!("Could not reach {}: {}", url, transport),
+    }
+}
+
+fn parse_submitted(line: &str) -> Option<Submitted> {
+    let mut fields = line.splitn(4, ' ');
+    let day = fields.next()?.parse::<u8>().ok()?;
+    let part = match fields.next()? {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return None,
+    };
+    let verdict = Verdict::parse(fields.next()?)?;
+    let answer = fields.next()?.replace("\\n", "\n");
+    Some(Submitted { day, part, verdict, answer })
+}
+
+/// The verdict for the answer if earlier submissions tell it: the same answer was sent
+/// before, the part has been solved with another answer, or a number is beyond a bound
+/// the server named.
+fn known_verdict(submitted: &[Submitted], day: u8, part: Part, answer: &str) -> Option<Verdict> {
+    let earlier = submitted.iter().filter(|submitted| submitted.day == day && submitted.part == part);
+    let number = answer.trim().parse::<i128>().ok();
+    for submitted in earlier {
+        if submitted.answer == answer {
+            return Some(submitted.verdict.clone());
+        }
+        match (&submitted.verdict, number, submitted.answer.trim().parse::<i128>().ok()) {
+            (Verdict::CORRECT, _, _) => return Some(Verdict::WRONG),
+            (Verdict::TOO_HIGH, Some(number), Some(bound)) if number >= bound => return Some(Verdict::TOO_HIGH),
+            (Verdict::TOO_LOW, Some(number), Some(bound)) if number <= bound => return Some(Verdict::TOO_LOW),
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::parameters::{self, Override};
+
+/// The config file read when no `--config` is given. It is optional.
+pub const DEFAULT_FILE: &str = "aoc.ini";
+
+/// Settings read from an INI style file:
+///
+/// ```text
+/// # comment
+/// [day15]
+/// row = 10
+/// ```
+#[derive(Debug, Default)]
+pub struct Config {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    section: String,
+    key: String,
+    value: String,
+}
+
+impl Config {
+    /// Reads the given file. A missing file is only an error if it was asked for explicitly.
+    pub fn load(file: Option<&Path>) -> Result<Self, String> {
+        let path = file.unwrap_or(Path::new(DEFAULT_FILE));
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text).map_err(|error| format!("{}: {}", path.display(), error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound && file.is_none() => Ok(Config::default()),
+            Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut entries = Vec::new();
+        let mut section = String::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| format!("line {}: expected [section] or <key> = <value>", number + 1))?;
+            entries.push(Entry { section: section.clone(), key: key.trim().to_string(), value: value.trim().to_string() });
+        }
+        Ok(Config { entries })
+    }
+
+    /// The value of `key` in `[section]`, the last one if it is given more than once.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.entries.iter().rev()
+            .find(|entry| entry.section == section && entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+
+    /// The puzzle parameters given in `[dayNN]` sections.
+    pub fn overrides(&self) -> Result<Vec<Override>, String> {
+        self.entries.iter()
+            .filter(|entry| entry.section.starts_with("day"))
+            .map(|entry| {
+                let day = parameters::parse_day(&entry.section)?;
+                let value = entry.value.parse::<i64>()
+                    .map_err(|_| format!("Value of '{}' in [{}] is not a number", entry.key, entry.section))?;
+                Ok(Override { day: Some(day), name: entry.key.clone(), value })
+            })
+            .collect()
+    }
+}
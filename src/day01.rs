@@ -1,54 +1,56 @@
-use crate::files;
+use crate::error::Error;
+use crate::parameters::Parameters;
+use crate::solution::{Answer, Solution};
 
 mod problem {
+    use crate::parse::{self, ParseError};
+
     pub struct Elf {
         calories: Vec<u32>,
     }
 
     impl Elf {
-        pub fn new() -> Self {
-            Elf { calories: Vec::new() }
+        /// Parses the food items of an elf, one number per line.
+        pub fn from(block: &str) -> Result<Self, ParseError> {
+            let calories = block.lines()
+                .map(parse::number::<u32>)
+                .collect::<Result<Vec<u32>, ParseError>>()?;
+            Ok(Elf { calories })
         }
 
         pub fn calories(&self) -> u32 {
-            return self.calories.iter().sum();
+            self.calories.iter().sum()
         }
     }
 
-    pub fn parse_elves_from(lines: Vec<String>) -> Vec<Elf> {
-        let mut elves = Vec::new();
-        let mut current = Elf::new();
-        for line in lines {
-            if line.is_empty() {
-                elves.push(current);
-                current = Elf::new();
-            } else {
-                let food = line.parse::<u32>().unwrap();
-                current.calories.push(food)
-            }
-        }
-        return elves;
+    pub fn parse_elves_from(input: &str) -> Result<Vec<Elf>, ParseError> {
+        parse::blocks(input).map(Elf::from).collect()
     }
 }
 
 
-pub fn solve() {
-    let file = "resources/day01.txt";
-
-    let lines = files::parse_lines_from(file);
-    let mut elves = problem::parse_elves_from(lines);
-
-    elves.sort_by(|a, b| a.calories().cmp(&b.calories()));
+pub struct Day01;
 
-    let calories = elves.last().unwrap().calories();
-    println!("Fattest 1 elf:   {} calories", calories);
+impl Solution for Day01 {
+    type Input = Vec<problem::Elf>;
 
-    let elf3 = elves.pop().unwrap();
-    let elf2 = elves.pop().unwrap();
-    let elf1 = elves.pop().unwrap();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let mut elves = problem::parse_elves_from(input)?;
+        elves.sort_by_key(|elf| elf.calories());
+        Ok(elves)
+    }
 
-    let sum = elf1.calories() + elf2.calories() + elf3.calories();
+    fn part1(elves: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let fattest = elves.last().ok_or_else(|| Error::no_answer("There are no elves"))?;
+        Ok(fattest.calories().into())
+    }
 
-    println!("Fattest 3 elves: {} calories", sum)
+    fn part2(elves: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let calories = elves.iter()
+            .rev()
+            .take(3)
+            .map(|elf| elf.calories())
+            .sum::<u32>();
+        Ok(calories.into())
+    }
 }
-
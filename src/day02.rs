@@ -1,18 +1,22 @@
-use crate::files;
+use crate::error::Error;
+use crate::parameters::Parameters;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 
-enum Shape {
+#[derive(Copy, Clone)]
+pub enum Shape {
     ROCK,
     PAPER,
     SCISSOR,
 }
 
 impl Shape {
-    pub fn new(shape: String) -> Self {
-        match shape.as_str() {
-            "A" | "X" => Shape::ROCK,
-            "B" | "Y" => Shape::PAPER,
-            "C" | "Z" => Shape::SCISSOR,
-            _ => panic!("Don't know shape '{}'", shape)
+    pub fn new(shape: &str) -> Result<Self, ParseError> {
+        match shape {
+            "A" | "X" => Ok(Shape::ROCK),
+            "B" | "Y" => Ok(Shape::PAPER),
+            "C" | "Z" => Ok(Shape::SCISSOR),
+            _ => Err(ParseError::new(shape, "Expected a shape 'A', 'B', 'C', 'X', 'Y' or 'Z'")),
         }
     }
 
@@ -44,40 +48,41 @@ impl Shape {
         }
     }
 
-    pub fn to_get(&self, result: Result) -> Shape {
+    pub fn to_get(self, result: Outcome) -> Shape {
         match self {
             Shape::ROCK => match result {
-                Result::LOSE => Shape::SCISSOR,
-                Result::DRAW => Shape::ROCK,
-                Result::WIN => Shape::PAPER,
+                Outcome::LOSE => Shape::SCISSOR,
+                Outcome::DRAW => Shape::ROCK,
+                Outcome::WIN => Shape::PAPER,
             },
             Shape::PAPER => match result {
-                Result::LOSE => Shape::ROCK,
-                Result::DRAW => Shape::PAPER,
-                Result::WIN => Shape::SCISSOR,
+                Outcome::LOSE => Shape::ROCK,
+                Outcome::DRAW => Shape::PAPER,
+                Outcome::WIN => Shape::SCISSOR,
             },
             Shape::SCISSOR => match result {
-                Result::LOSE => Shape::PAPER,
-                Result::DRAW => Shape::SCISSOR,
-                Result::WIN => Shape::ROCK,
+                Outcome::LOSE => Shape::PAPER,
+                Outcome::DRAW => Shape::SCISSOR,
+                Outcome::WIN => Shape::ROCK,
             },
         }
     }
 }
 
-enum Result {
+#[derive(Copy, Clone)]
+pub enum Outcome {
     LOSE,
     DRAW,
     WIN,
 }
 
-impl Result {
-    pub fn new(result: String) -> Self {
-        match result.as_str() {
-            "X" => Result::LOSE,
-            "Y" => Result::DRAW,
-            "Z" => Result::WIN,
-            _ => panic!("Don't know result '{}'", result)
+impl Outcome {
+    pub fn new(result: &str) -> Result<Self, ParseError> {
+        match result {
+            "X" => Ok(Outcome::LOSE),
+            "Y" => Ok(Outcome::DRAW),
+            "Z" => Ok(Outcome::WIN),
+            _ => Err(ParseError::new(result, "Expected a result 'X', 'Y' or 'Z'")),
         }
     }
 }
@@ -89,54 +94,69 @@ struct Match {
 
 impl Match {
     pub fn new(played: Shape, response: Shape) -> Self {
-        return Match { played, response };
+        Match { played, response }
     }
 
-    pub fn new_cheated(played: Shape, result: Result) -> Self {
+    pub fn new_cheated(played: Shape, result: Outcome) -> Self {
         let i_play = played.to_get(result);
-        return Match::new(played, i_play);
+        Match::new(played, i_play)
     }
 
     pub fn evaluate(&self) -> u32 {
         let base = self.response.points();
         let outcome = self.response.against(&self.played);
-        return base + outcome;
+        base + outcome
     }
 }
 
-pub fn solve() {
-    let file = "resources/day02.txt";
-    let lines = files::parse_lines_from(file);
-
-    let according_to_guess = |line: &String| {
-        let separated: Vec<&str> = line.split(" ").collect();
-        let played = separated.first().unwrap().to_string();
-        let played = Shape::new(played);
-        let response = separated.last().unwrap().to_string();
-        let response = Shape::new(response);
-        Match::new(played, response)
-    };
-
-    let sum: u32 = lines.iter()
-        .map(according_to_guess)
-        .map(|m| m.evaluate())
-        .sum();
-
-    println!("Puzzle 1 sum: {}", sum);
-
-    let according_to_meaning = |line: &String| {
-        let separated: Vec<&str> = line.split(" ").collect();
-        let played = separated.first().unwrap().to_string();
-        let played = Shape::new(played);
-        let result = separated.last().unwrap().to_string();
-        let result = Result::new(result);
-        Match::new_cheated(played, result)
-    };
-
-    let sum: u32 = lines.iter()
-        .map(according_to_meaning)
-        .map(|m| m.evaluate())
-        .sum();
-
-    println!("Puzzle 2 sum: {}", sum);
-}
\ No newline at end of file
+/// One line of the strategy guide. Part 1 reads the second column as our response,
+/// part 2 as the result the round has to end with.
+pub struct Round {
+    played: Shape,
+    response: Shape,
+    result: Outcome,
+}
+
+impl Round {
+    pub fn new(line: &str) -> Result<Self, ParseError> {
+        let (played, second) = parse::split_once(line, " ")?;
+        Ok(Round {
+            played: Shape::new(played)?,
+            response: Shape::new(second)?,
+            result: Outcome::new(second)?,
+        })
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let strategy = input.lines()
+            .map(Round::new)
+            .collect::<Result<Vec<Round>, ParseError>>()?;
+        Ok(strategy)
+    }
+
+    fn part1(strategy: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let according_to_guess = |round: &Round| Match::new(round.played, round.response);
+
+        let sum = strategy.iter()
+            .map(according_to_guess)
+            .map(|m| m.evaluate())
+            .sum::<u32>();
+        Ok(sum.into())
+    }
+
+    fn part2(strategy: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let according_to_meaning = |round: &Round| Match::new_cheated(round.played, round.result);
+
+        let sum = strategy.iter()
+            .map(according_to_meaning)
+            .map(|m| m.evaluate())
+            .sum::<u32>();
+        Ok(sum.into())
+    }
+}
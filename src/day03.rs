@@ -1,6 +1,9 @@
 use std::ffi::c_uint;
 
-use crate::files;
+use crate::error::Error;
+use crate::parameters::Parameters;
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
 
 const LOWER_CASE_OFFSET: u32 = 96;
 const UPPER_CASE_OFFSET: u32 = 38;
@@ -14,40 +17,51 @@ pub fn priority_of(char: char) -> u32 {
     }
 }
 
-pub fn find_same_char_from(strings: Vec<String>) -> char {
-    let mut vec = strings.clone();
+pub fn find_same_char_from(strings: Vec<String>) -> Option<char> {
+    let mut vec = strings;
     let (intersection, others) = vec.split_at_mut(1);
     let intersection = &mut intersection[0];
     for other in others {
         intersection.retain(|e| other.contains(e));
     }
-    intersection.pop().unwrap()
+    intersection.pop()
 }
 
-pub fn solve() {
-    let file = "resources/day03.txt";
-    let lines = files::parse_lines_from(file);
-
-    let sum: u32 = lines.iter()
-        .map(|line| {
-            let compartment = line.split_at(line.len() / 2);
-            let mut strings = Vec::new();
-            strings.push(compartment.0.to_string());
-            strings.push(compartment.1.to_string());
-            let same_char = find_same_char_from(strings);
-            priority_of(same_char)
-        })
-        .sum();
-
-    println!("Sum of priorities: {}", sum);
-
-    let chunks: Vec<&[String]> = lines.chunks(3).collect();
-
-    let sum: u32 = chunks.into_iter().map(|chunk| {
-        let strings = chunk.iter().map(|element| element.clone()).collect();
-        let same_char = find_same_char_from(strings);
-        priority_of(same_char)
-    }).sum();
-
-    println!("Sum of priorities: {}", sum);
-}
\ No newline at end of file
+fn priority_of_same_char_from(strings: Vec<String>) -> Result<u32, Error> {
+    find_same_char_from(strings)
+        .map(priority_of)
+        .ok_or_else(|| Error::no_answer("Rucksacks have no item in common"))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input.lines()
+            .map(|line| match line.find(|c: char| !c.is_ascii_alphabetic()) {
+                Some(index) => Err(ParseError::new(&line[index..], "Expected an item 'a'-'z' or 'A'-'Z'").into()),
+                None => Ok(line.to_string()),
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let sum = lines.iter()
+            .map(|line| {
+                let compartment = line.split_at(line.len() / 2);
+                let strings = vec![compartment.0.to_string(), compartment.1.to_string()];
+                priority_of_same_char_from(strings)
+            })
+            .sum::<Result<u32, Error>>()?;
+        Ok(sum.into())
+    }
+
+    fn part2(lines: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let sum = lines.chunks(3)
+            .map(|chunk| priority_of_same_char_from(chunk.to_vec()))
+            .sum::<Result<u32, Error>>()?;
+        Ok(sum.into())
+    }
+}
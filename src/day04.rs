@@ -1,56 +1,47 @@
-use crate::files;
-
-struct Range {
-    lower: u32,
-    upper: u32,
+use crate::error::Error;
+use crate::interval::IntervalSet;
+use crate::parameters::Parameters;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The sections assigned to an elf, written like `2-4`.
+pub fn parse_sections(range: &str) -> Result<IntervalSet<u32>, ParseError> {
+    let (lower, upper) = parse::split_once(range, "-")?;
+    let lower = parse::number::<u32>(lower)?;
+    let upper = parse::number::<u32>(upper)?;
+    if lower > upper {
+        return Err(ParseError::new(range, "Expected the first section to be at most the last one"));
+    }
+    let mut sections = IntervalSet::new();
+    sections.insert(lower..=upper);
+    Ok(sections)
 }
 
-impl Range {
-    pub fn new(range: &str) -> Self {
-        let mut split = range.splitn(2, "-");
-        let lower = split.next().unwrap().parse::<u32>().unwrap();
-        let upper = split.next().unwrap().parse::<u32>().unwrap();
-        Range { lower, upper }
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<(IntervalSet<u32>, IntervalSet<u32>)>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let pairs = input.lines()
+            .map(|line| {
+                let (first, last) = parse::split_once(line, ",")?;
+                Ok((parse_sections(first)?, parse_sections(last)?))
+            }).collect::<Result<Self::Input, ParseError>>()?;
+        Ok(pairs)
     }
 
-    pub fn fully_includes(&self, other: &Range) -> bool {
-        other.lower >= self.lower && other.upper <= self.upper
+    fn part1(pairs: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let contain = pairs.iter()
+            .filter(|(first, last)| first.is_superset(last) || last.is_superset(first))
+            .count();
+        Ok(contain.into())
     }
 
-    pub fn overlap(&self, other: &Range) -> bool {
-        self.lower >= other.lower && self.lower <= other.upper
+    fn part2(pairs: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let overlap = pairs.iter()
+            .filter(|(first, last)| first.overlaps(last))
+            .count();
+        Ok(overlap.into())
     }
 }
-
-pub fn solve() {
-    let file = "resources/day04.txt";
-    let lines = files::parse_lines_from(file);
-
-    let ranges: Vec<(Range, Range)> = lines
-        .iter()
-        .map(|line| {
-            let mut pairs = line.splitn(2, ",");
-            let first = pairs.next().unwrap();
-            let last = pairs.next().unwrap();
-            let first = Range::new(first);
-            let last = Range::new(last);
-            (first, last)
-        }).collect::<Vec<(Range, Range)>>();
-
-    let contain = ranges
-        .iter()
-        .filter(|(first, last)| {
-            first.fully_includes(last) || last.fully_includes(first)
-        })
-        .count();
-
-    println!("Fully contain the other: {}", contain);
-
-    let overlap = ranges.iter()
-        .filter(|(first, last)| {
-            first.overlap(last) || last.overlap(first)
-        })
-        .count();
-
-    println!("Overlap each other: {}", overlap)
-}
\ No newline at end of file
@@ -1,7 +1,14 @@
-use crate::files;
+use std::fmt;
+
+use crate::error::Error;
+use crate::parameters::Parameters;
+use crate::parse::{self, ParseError};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Answer, Solution};
+use crate::trace::{self, Level};
 
 #[derive(Clone)]
-struct Stacks {
+pub struct Stacks {
     stacks: Vec<Vec<char>>,
 }
 
@@ -31,7 +38,7 @@ impl Stacks {
         _to.extend(pops);
     }
 
-    pub fn stack_message(self) -> String {
+    pub fn stack_message(&self) -> String {
         self.stacks.iter()
             .map(|stack| stack.last().unwrap_or(&' ').to_string())
             .collect::<Vec<String>>()
@@ -39,81 +46,183 @@ impl Stacks {
     }
 }
 
+/// Draws the stacks like the puzzle does, with the numbers of the stacks below them.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let line = self.stacks.iter()
+                .map(|stack| stack.get(level).map(|c| format!("[{}]", c)).unwrap_or_else(|| "   ".to_string()))
+                .collect::<Vec<String>>()
+                .join(" ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        let numbers = (1..=self.stacks.len())
+            .map(|number| format!(" {} ", number))
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "{}", numbers.trim_end())
+    }
+}
+
+
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Model {
+    /// Moves one crate at a time.
+    CRATE_MOVER_9000,
+    /// Moves all crates of an instruction at once, keeping their order.
+    CRATE_MOVER_9001,
+}
+
+/// A crane rearranging the stacks, one instruction per step.
+pub struct Crane<'a> {
+    model: Model,
+    stacks: Stacks,
+    instructions: &'a [Instruction],
+    executed: usize,
+}
+
+impl<'a> Crane<'a> {
+    pub fn new(model: Model, stacks: Stacks, instructions: &'a [Instruction]) -> Self {
+        Crane { model, stacks, instructions, executed: 0 }
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.stacks
+    }
+}
+
+impl Simulation for Crane<'_> {
+    type Snapshot = Stacks;
+
+    fn step(&mut self) {
+        let Some(instruction) = self.instructions.get(self.executed) else { return };
+        trace::event(Level::TRACE, "move", &[
+            ("count", instruction._move.into()),
+            ("from", (instruction._from + 1).into()),
+            ("to", (instruction._to + 1).into()),
+        ]);
+        match self.model {
+            Model::CRATE_MOVER_9000 => self.stacks.execute_unordered(instruction),
+            Model::CRATE_MOVER_9001 => self.stacks.execute_ordered(instruction),
+        }
+        self.executed += 1;
+    }
 
-struct Instruction {
+    fn is_done(&self) -> bool {
+        self.executed == self.instructions.len()
+    }
+
+    fn snapshot(&self) -> Stacks {
+        self.stacks.clone()
+    }
+}
+
+
+pub struct Instruction {
     _move: usize,
     _from: usize,
     _to: usize,
 }
 
 impl Instruction {
-    pub fn new(string: &String) -> Self {
-        let instruction = string.split(" ").collect::<Vec<&str>>();
-        Instruction {
-            _move: instruction.get(1).unwrap().parse::<usize>().unwrap(),
-            _from: instruction.get(3).unwrap().parse::<usize>().unwrap() - 1,
-            _to: instruction.get(5).unwrap().parse::<usize>().unwrap() - 1,
+    pub fn new(string: &str, stacks: usize) -> Result<Self, ParseError> {
+        let instruction = string.split(' ').collect::<Vec<&str>>();
+        if instruction.len() != 6 || instruction[0] != "move" || instruction[2] != "from" || instruction[4] != "to" {
+            return Err(ParseError::new(string, "Expected 'move <count> from <stack> to <stack>'"));
         }
+        let stack = |word: &str| match parse::number::<usize>(word)? {
+            number if number == 0 || number > stacks => {
+                Err(ParseError::new(word, format!("Expected a stack between 1 and {}", stacks)))
+            }
+            number => Ok(number - 1),
+        };
+        Ok(Instruction {
+            _move: parse::number::<usize>(instruction[1])?,
+            _from: stack(instruction[3])?,
+            _to: stack(instruction[5])?,
+        })
     }
 }
 
 
-fn prepare(lines: &Vec<String>) -> (Stacks, Vec<Instruction>) {
-    let (stacks, instructions): (Vec<_>, Vec<_>) = lines
-        .into_iter()
+fn prepare(input: &str) -> Result<(Stacks, Vec<Instruction>), ParseError> {
+    let (stacks, instructions): (Vec<_>, Vec<_>) = input
+        .lines()
         .partition(|line| !line.starts_with("move"));
 
-    let mut stacks: Vec<&String> = stacks
+    let mut stacks: Vec<&str> = stacks
         .into_iter()
         .filter(|line| !line.is_empty())
         .rev()
         .collect();
 
-    let count = stacks.remove(0)
+    if stacks.is_empty() {
+        return Err(ParseError::new(input, "Expected a drawing of the stacks before the first move"));
+    }
+    let numbers = stacks.remove(0);
+    let count = numbers
         .split_whitespace()
         .last()
-        .unwrap()
-        .parse::<usize>()
-        .unwrap();
+        .map(parse::number::<usize>)
+        .unwrap_or_else(|| Err(ParseError::new(numbers, "Expected the stack numbers")))?;
 
     let stacks: Vec<_> = stacks.iter()
         .map(|tower| tower.chars().collect::<Vec<char>>())
-        .map(|line| line.chunks(4).map(|ch| ch[1]).collect::<Vec<char>>())
+        .map(|line| line.chunks(4).map(|ch| ch.get(1).copied().unwrap_or(' ')).collect::<Vec<char>>())
         .collect();
 
     let stacks: Vec<Vec<char>> = (0..count)
         .map(|index| stacks.iter()
-            .map(move |vertical| vertical.get(index).unwrap())
-            .filter(|c| **c != ' ')
-            .map(|c| *c)
+            .map(move |vertical| vertical.get(index).copied().unwrap_or(' '))
+            .filter(|c| *c != ' ')
             .collect::<Vec<char>>())
         .collect();
 
+    // Both models move the same number of crates, so the heights of the stacks tell whether
+    // every move finds enough crates to take.
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
     let playground = Stacks::new(stacks);
 
-    let instructions: Vec<Instruction> = instructions
-        .into_iter()
-        .map(|line| Instruction::new(line))
-        .collect();
+    let moves: Vec<Instruction> = instructions.iter()
+        .map(|line| Instruction::new(line, count))
+        .collect::<Result<Vec<Instruction>, ParseError>>()?;
+
+    for (line, instruction) in instructions.iter().zip(&moves) {
+        let available = heights[instruction._from];
+        if instruction._move > available {
+            let crates = line.split(' ').nth(1).unwrap_or(line);
+            return Err(ParseError::new(crates, format!("Expected at most {} crates, stack {} holds no more", available, instruction._from + 1)));
+        }
+        heights[instruction._from] -= instruction._move;
+        heights[instruction._to] += instruction._move;
+    }
 
-    return (playground, instructions);
+    Ok((playground, moves))
 }
 
 
-pub fn solve() {
-    let file = "resources/day05.txt";
-    let lines = files::parse_lines_from(file);
+pub struct Day05;
 
-    let (stacks, instructions) = prepare(&lines);
+impl Solution for Day05 {
+    type Input = (Stacks, Vec<Instruction>);
 
-    let mut stack_task_1 = stacks.clone();
-    let mut stack_task_2 = stacks.clone();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(prepare(input)?)
+    }
+
+    fn part1((stacks, instructions): &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        Ok(rearrange(Model::CRATE_MOVER_9000, stacks, instructions).into())
+    }
 
-    for instruction in instructions {
-        stack_task_1.execute_unordered(&instruction);
-        stack_task_2.execute_ordered(&instruction);
+    fn part2((stacks, instructions): &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        Ok(rearrange(Model::CRATE_MOVER_9001, stacks, instructions).into())
     }
+}
 
-    println!("Unordered  {}", stack_task_1.stack_message());
-    println!("Ordered    {}", stack_task_2.stack_message());
+fn rearrange(model: Model, stacks: &Stacks, instructions: &[Instruction]) -> String {
+    let mut driver = Driver::new(Crane::new(model, stacks.clone(), instructions));
+    driver.run();
+    driver.simulation().stacks().stack_message()
 }
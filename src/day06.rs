@@ -1,6 +1,8 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::files;
+use crate::error::Error;
+use crate::parameters::{Parameter, Parameters};
+use crate::solution::{Answer, Solution};
 
 struct MarkerDetector {
     elements: VecDeque<char>,
@@ -31,26 +33,44 @@ impl MarkerDetector {
 }
 
 
-pub fn solve() {
-    let file = "resources/day06.txt";
-    let lines = files::parse_lines_from(file);
+fn find_marker(datastream: &str, marker_size: u32) -> Option<u32> {
+    let mut detector = MarkerDetector::new(marker_size);
+    for c in datastream.chars() {
+        detector.parse(c);
+        if detector.marker_found() {
+            return Some(detector.position);
+        }
+    }
+    None
+}
 
-    let mut packet_detector = MarkerDetector::new(4);
+pub struct Day06;
 
-    for c in lines.get(0).unwrap().chars() {
-        packet_detector.parse(c);
-        if packet_detector.marker_found() {
-            println!("start-of-packet marker: {}", packet_detector.position);
-            break;
-        }
+impl Solution for Day06 {
+    type Input = String;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter { name: "packet_marker", description: "distinct characters of a start-of-packet marker", puzzle: 4, example: 4 },
+        Parameter { name: "message_marker", description: "distinct characters of a start-of-message marker", puzzle: 14, example: 14 },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().next().unwrap().to_string())
     }
 
-    let mut message_detector = MarkerDetector::new(14);
-    for c in lines.get(0).unwrap().chars() {
-        message_detector.parse(c);
-        if message_detector.marker_found() {
-            println!("start-of-message marker: {}", message_detector.position);
-            break;
-        }
+    fn part1(datastream: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let position = find_marker(datastream, marker_length(parameters, "packet_marker")?)
+            .ok_or_else(|| Error::no_answer("No start-of-packet marker found"))?;
+        Ok(position.into())
+    }
+
+    fn part2(datastream: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let position = find_marker(datastream, marker_length(parameters, "message_marker")?)
+            .ok_or_else(|| Error::no_answer("No start-of-message marker found"))?;
+        Ok(position.into())
     }
 }
+
+fn marker_length(parameters: &Parameters, name: &str) -> Result<u32, Error> {
+    parameters.at_least(name, 1, "a marker needs at least one character")
+}
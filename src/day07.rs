@@ -1,28 +1,24 @@
 use std::collections::{HashMap, VecDeque};
 
-use crate::files;
+use crate::error::Error;
+use crate::parameters::{Parameter, Parameters};
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::trace::{self, Level};
 
-const DISK_SPACE: u32 = 70000000;
-const REQUIRED_SPACE: u32 = 30000000;
 
 #[derive(Clone)]
 struct File {
     size: usize,
 }
 
-pub fn solve() {
-    let file = "resources/day07.txt";
-    let lines = files::parse_string_from(file)
-        .expect(&*(" Could not read file ".to_owned() + file));
-
+fn scan_file_system(lines: &str) -> Result<HashMap<String, Vec<File>>, ParseError> {
     let lines = lines.trim();
 
     let commands = lines.split("$ ")
-        .into_iter()
         .map(|command| command.trim())
-        .map(|command| command.to_owned())
-        .skip_while(|command| command.is_empty() || command == "cd /")
-        .collect::<Vec<String>>();
+        .skip_while(|command| command.is_empty() || *command == "cd /")
+        .collect::<Vec<&str>>();
 
     let mut map: HashMap<String, Vec<File>> = HashMap::new();
     let mut path = VecDeque::new();
@@ -35,70 +31,85 @@ pub fn solve() {
             let option = command.split_whitespace().last().unwrap().to_string();
             path.push_back(option);
         } else if command.starts_with("ls") {
-            let files: _ = command
-                .split("\n")
+            let files = command
+                .split('\n')
                 .skip(1)
                 .filter(|line| !line.starts_with("dir"))
                 .map(|line| {
-                    let split: Vec<_> = line.split(' ').collect();
-                    let size = split.first().unwrap().parse::<usize>().unwrap();
-                    File { size }
-                }).collect::<Vec<File>>();
+                    let (size, _name) = parse::split_once(line, " ")?;
+                    let size = parse::number::<usize>(size)?;
+                    Ok(File { size })
+                }).collect::<Result<Vec<File>, ParseError>>()?;
 
             let mut copied = path.clone();
-            while copied.len() > 0 {
+            while !copied.is_empty() {
                 let directory = Vec::from_iter(&copied)
                     .iter()
                     .map(|s| s.to_string())
                     .collect::<Vec<String>>()
                     .join("/");
-                if map.contains_key(&directory) {
-                    let x = map.get_mut(&directory).unwrap();
-                    x.extend(files.clone())
-                } else {
-                    let mut n = Vec::new();
-                    n.extend(files.clone());
-                    map.insert(directory, n);
-                }
+                map.entry(directory).or_default().extend(files.clone());
                 copied.pop_back();
             }
+        } else {
+            return Err(ParseError::new(command, "Expected a command 'cd' or 'ls'"));
         }
     }
 
-    let sum: usize = map.iter()
-        .map(|e| {
-            let x2: usize = e.1.iter()
+    Ok(map)
+}
+
+fn directory_sizes(map: &HashMap<String, Vec<File>>) -> HashMap<String, usize> {
+    map.iter()
+        .map(|(directory, files)| {
+            let size: usize = files.iter()
                 .map(|file| file.size)
                 .sum();
-            x2
+            (directory.clone(), size)
         })
-        .filter(|s| s <= &(100_000 as usize))
-        .sum();
-
-    println!("-> Sum {}", sum);
+        .collect()
+}
 
-    let used_space: usize = map.get(".")
-        .unwrap().iter()
-        .map(|f| f.size)
-        .sum::<usize>();
+pub struct Day07;
 
-    println!("Used space {}", used_space);
+impl Solution for Day07 {
+    type Input = HashMap<String, usize>;
 
-    let free_space: u32 = DISK_SPACE - used_space as u32;
-    println!("Free space {}", free_space);
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter { name: "small_directory", description: "largest directory size counted in part 1", puzzle: 100_000, example: 100_000 },
+        Parameter { name: "disk_space", description: "total disk space", puzzle: 70_000_000, example: 70_000_000 },
+        Parameter { name: "required_space", description: "free space the update needs", puzzle: 30_000_000, example: 30_000_000 },
+    ];
 
-    let required_space: u32 = REQUIRED_SPACE - free_space as u32;
-    println!("Required space {}", required_space);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(directory_sizes(&scan_file_system(input)?))
+    }
 
-    let size = map.iter()
-        .map(|e| {
-            let x2: usize = e.1.iter()
-                .map(|file| file.size)
-                .sum();
-            x2
-        })
-        .filter(|s| s >= &(required_space as usize))
-        .min().unwrap();
+    fn part1(sizes: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let small_directory: usize = parameters.get("small_directory")?;
+        let sum = sizes.values()
+            .filter(|s| s <= &&small_directory)
+            .sum::<usize>();
+        Ok(sum.into())
+    }
 
-    println!("-> Size of file to be deleted {}", size)
+    fn part2(sizes: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let disk_space: usize = parameters.get("disk_space")?;
+        let required_space: usize = parameters.get("required_space")?;
+        let used_space = *sizes.get(".")
+            .ok_or_else(|| Error::no_answer("The terminal output lists no files"))?;
+        let free_space = disk_space.saturating_sub(used_space);
+        let required_space = required_space.saturating_sub(free_space);
+        trace::event(Level::DEBUG, "space", &[
+            ("used", used_space.into()),
+            ("free", free_space.into()),
+            ("to_free", required_space.into()),
+        ]);
+
+        let size = sizes.values()
+            .filter(|s| s >= &&required_space)
+            .min()
+            .ok_or_else(|| Error::no_answer("No directory is large enough to free the required space"))?;
+        Ok((*size).into())
+    }
 }
@@ -1,175 +1,122 @@
-use std::cmp;
-
-use crate::files;
+use crate::error::Error;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::parameters::Parameters;
+use crate::parse::ParseError;
+use crate::render::{Colour, Pictures};
+use crate::solution::{Answer, Part, Solution};
 
 const RADIX: u32 = 10;
 
-struct Grid {
-    size: usize,
-    elements: Vec<usize>,
-}
-
-impl Grid {
-    pub fn new(string: String) -> Self {
-        let size = string.find("\n").unwrap() as usize;
-        let elements = string.chars()
-            .filter(|c| c != &'\n')
-            .map(|c| c.to_digit(RADIX).unwrap() as usize)
-            .collect::<Vec<usize>>();
-        Grid { size, elements }
-    }
-
-    pub fn can_view_border(&self, x: usize, y: usize) -> bool {
-        self.can_view_left_border(x, y) ||
-            self.can_view_right_border(x, y) ||
-            self.can_view_upper_border(x, y) ||
-            self.can_view_bottom_border(x, y)
-    }
+/// The height 0-9 of every tree.
+pub type Forest = Grid<u32>;
 
-    fn item(&self, x: usize, y: usize) -> &usize {
-        &self.elements[x + y * self.size]
-    }
-
-    fn can_view_left_border(&self, x: usize, y: usize) -> bool {
-        let item = self.item(x, y);
-        self.elements_left_of(x, y)
-            .iter()
-            .all(|e| e < &item)
-    }
+pub fn parse_forest(string: &str) -> Result<Forest, ParseError> {
+    Grid::parse(string, |c| c.to_digit(RADIX).ok_or_else(|| "Expected a tree height 0-9".to_string()))
+}
 
-    fn can_view_right_border(&self, x: usize, y: usize) -> bool {
-        let item = self.item(x, y);
-        self.elements_right_of(x, y)
-            .iter()
-            .all(|e| e < &item)
-    }
+/// Low trees are brown, high trees green.
+pub fn picture(forest: &Forest) -> Grid<Colour> {
+    forest.map(|height| Colour::BROWN.blend(Colour::GREEN, *height as f64 / 9.0))
+}
 
-    fn can_view_upper_border(&self, x: usize, y: usize) -> bool {
-        let item = self.item(x, y);
-        self.elements_above_of(x, y)
-            .iter()
-            .all(|e| e < &item)
+/// Whether every tree can be seen from outside the forest, looking along every row and
+/// column once while remembering the highest tree so far.
+pub fn visible_trees(forest: &Forest) -> Grid<bool> {
+    let mut visible = Grid::filled(forest.width(), forest.height(), false);
+    for line in lines_of_sight(forest) {
+        let mut highest = None;
+        for tree in line {
+            if highest.is_none_or(|highest| forest[tree] > highest) {
+                visible[tree] = true;
+                highest = Some(forest[tree]);
+            }
+        }
     }
+    visible
+}
 
-    fn can_view_bottom_border(&self, x: usize, y: usize) -> bool {
-        let item = self.item(x, y);
-        self.elements_below_of(x, y)
-            .iter()
-            .all(|e| e < &item)
+/// The scenic score of every tree. Looking along every row and column once, a stack keeps
+/// the trees that can still block the view of a following tree.
+pub fn scenic_scores(forest: &Forest) -> Grid<usize> {
+    let mut scores = Grid::filled(forest.width(), forest.height(), 1);
+    for line in lines_of_sight(forest) {
+        let mut blocking: Vec<usize> = Vec::new();
+        for (index, tree) in line.iter().enumerate() {
+            while blocking.last().is_some_and(|last| forest[line[*last]] < forest[*tree]) {
+                blocking.pop();
+            }
+            scores[*tree] *= index - blocking.last().copied().unwrap_or(0);
+            blocking.push(index);
+        }
     }
+    scores
+}
 
-    fn elements_left_of(&self, x: usize, y: usize) -> Vec<&usize> {
-        let start = y * self.size;
-        let end = x + y * self.size;
-        self.elements[start..end]
-            .iter()
-            .collect::<Vec<&usize>>()
-    }
+/// Every row and column in both directions.
+fn lines_of_sight(forest: &Forest) -> Vec<Vec<Point<usize>>> {
+    let (width, height) = (forest.width(), forest.height());
+    let rows = (0..height).map(|y| (0..width).map(|x| Point::new(x, y)).collect::<Vec<Point<usize>>>());
+    let columns = (0..width).map(|x| (0..height).map(|y| Point::new(x, y)).collect::<Vec<Point<usize>>>());
+    let lines = rows.chain(columns).collect::<Vec<Vec<Point<usize>>>>();
+    let reversed = lines.iter().map(|line| line.iter().rev().copied().collect()).collect::<Vec<Vec<Point<usize>>>>();
+    lines.into_iter().chain(reversed).collect()
+}
 
-    fn elements_right_of(&self, x: usize, y: usize) -> Vec<&usize> {
-        let start = y * self.size + x + 1;
-        let end = (y + 1) * self.size;
-        self.elements[start..end]
-            .iter()
-            .collect::<Vec<&usize>>()
-    }
+/// Slow but obviously correct version of [`visible_trees`] for a single tree.
+pub fn can_view_border(forest: &Forest, tree: Point<usize>) -> bool {
+    let height = forest[tree];
+    Direction::ORTHOGONAL.into_iter()
+        .any(|direction| forest.ray(tree, direction).all(|other| forest[other] < height))
+}
 
-    fn elements_above_of(&self, x: usize, y: usize) -> Vec<&usize> {
-        let start = x;
-        let end = y * self.size + x;
-        self.elements[start..end]
-            .iter()
-            .step_by(self.size)
-            .collect::<Vec<&usize>>()
-    }
+/// Slow but obviously correct version of [`scenic_scores`] for a single tree.
+pub fn scenic_score_of(forest: &Forest, tree: Point<usize>) -> usize {
+    Direction::ORTHOGONAL.into_iter()
+        .map(|direction| viewing_distance(forest, tree, direction))
+        .product()
+}
 
-    fn elements_below_of(&self, x: usize, y: usize) -> Vec<&usize> {
-        let end = self.size * self.size;
-        let start = (y + 1) * self.size + x;
-        let start = cmp::min(start, end);
-        self.elements[start..end]
-            .iter()
-            .step_by(self.size)
-            .collect::<Vec<&usize>>()
+/// Counts the trees seen in one direction, up to and including the first one that is
+/// at least as high as the tree itself.
+fn viewing_distance(forest: &Forest, tree: Point<usize>, direction: Direction) -> usize {
+    let height = forest[tree];
+    let mut distance = 0;
+    for other in forest.ray(tree, direction) {
+        distance += 1;
+        if forest[other] >= height {
+            break;
+        }
     }
+    distance
+}
 
-    pub fn scenic_score_of(&self, x: usize, y: usize) -> usize {
-        let left = self.viewing_distance_left_of(x, y);
-        let right = self.viewing_distance_right_of(x, y);
-        let above = self.viewing_distance_above_of(x, y);
-        let below = self.viewing_distance_below_of(x, y);
-        left * right * above * below
-    }
 
-    fn viewing_distance_left_of(&self, x: usize, y: usize) -> usize {
-        let item = self.item(x, y);
-        let elements = self.elements_left_of(x, y);
-        let smaller = elements
-            .iter()
-            .rev()
-            .take_while(|e| e < &&item)
-            .count();
-        cmp::min(smaller + 1, elements.len())
-    }
+pub struct Day08;
 
-    fn viewing_distance_right_of(&self, x: usize, y: usize) -> usize {
-        let item = self.item(x, y);
-        let elements = self.elements_right_of(x, y);
-        let smaller = elements
-            .iter()
-            .take_while(|e| e < &&item)
-            .count();
-        cmp::min(smaller + 1, elements.len())
-    }
+impl Solution for Day08 {
+    type Input = Forest;
 
-    fn viewing_distance_above_of(&self, x: usize, y: usize) -> usize {
-        let item = self.item(x, y);
-        let elements = self.elements_above_of(x, y);
-        let smaller = elements
-            .iter()
-            .rev()
-            .take_while(|e| e < &&item)
-            .count();
-        cmp::min(smaller + 1, elements.len())
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_forest(input)?)
     }
 
-    fn viewing_distance_below_of(&self, x: usize, y: usize) -> usize {
-        let item = self.item(x, y);
-        let elements = self.elements_below_of(x, y);
-        let smaller = elements
-            .iter()
-            .take_while(|e| e < &&item)
+    fn part1(forest: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let sum = visible_trees(forest).iter()
+            .filter(|(_, visible)| **visible)
             .count();
-        cmp::min(smaller + 1, elements.len())
+        Ok(sum.into())
     }
-}
-
 
-pub fn solve() {
-    let file = "resources/day08.txt";
-    let lines = files::parse_string_from(file).unwrap();
-    let grid = Grid::new(lines);
-
-    let mut sum = 0;
-    for x in 0..grid.size {
-        for y in 0..grid.size {
-            if grid.can_view_border(x, y) {
-                sum += 1;
-            }
-        }
+    fn part2(forest: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let scenic = scenic_scores(forest).iter()
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or(0);
+        Ok(scenic.into())
     }
 
-    println!("Visible outside: {}", sum);
-
-    let mut scenic = 0;
-    for x in 0..grid.size {
-        for y in 0..grid.size {
-            let new_scenic = grid.scenic_score_of(x, y);
-            if new_scenic > scenic {
-                scenic = new_scenic;
-            }
-        }
+    fn pictures(forest: &Self::Input, _: Part, _: &Parameters) -> Result<Pictures, Error> {
+        Ok(vec![picture(forest)])
     }
-
-    println!("Scenic score: {}", scenic);
 }
@@ -1,124 +1,178 @@
-use std::borrow::{Borrow, BorrowMut};
-use std::cmp;
 use std::collections::HashSet;
 
-use crate::files;
-
-#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+use crate::error::Error;
+use crate::geometry::{BoundingBox, Direction, Point};
+use crate::grid::Grid;
+use crate::parameters::{Parameter, Parameters};
+use crate::parse::{self, ParseError};
+use crate::render::{self, Colour, Pictures};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Clone)]
-struct Movement {
-    dx: i32,
-    dy: i32,
+pub struct Movement {
+    direction: Direction,
     times: u32,
 }
 
 impl Movement {
-    pub fn new(dx: i32, dy: i32, times: &str) -> Self {
-        Movement { dx, dy, times: times.trim().parse::<u32>().unwrap() }
+    pub fn new(direction: Direction, times: &str) -> Result<Self, ParseError> {
+        Ok(Movement { direction, times: parse::number::<u32>(times.trim())? })
     }
 
-    pub fn flatten(&self) -> Vec<Movement> {
-        let movement = Movement { dx: self.dx, dy: self.dy, times: 1 };
-        vec![movement; self.times as usize]
+    pub fn flatten(&self) -> Vec<Direction> {
+        vec![self.direction; self.times as usize]
     }
 }
 
-fn parse_movements(lines: Vec<String>) -> Vec<Movement> {
-    lines.iter()
-        .map(|movement| match movement.split_at(1) {
-            ("L", n) => Movement::new(-1, 0, n),
-            ("R", n) => Movement::new(1, 0, n),
-            ("U", n) => Movement::new(0, 1, n),
-            ("D", n) => Movement::new(0, -1, n),
-            (_, _) => panic!("Not reachable"),
+fn parse_movements(input: &str) -> Result<Vec<Direction>, ParseError> {
+    let movements = input.lines()
+        .map(|movement| {
+            let (direction, times) = parse::split_once(movement, " ")?;
+            Movement::new(Direction::parse(direction)?, times)
         })
+        .collect::<Result<Vec<Movement>, ParseError>>()?;
+    Ok(movements.iter()
         .flat_map(|movement| movement.flatten())
-        .collect::<Vec<Movement>>()
+        .collect::<Vec<Direction>>())
 }
 
-struct Rope {
-    size: u32,
-    elements: Vec<Point>,
-    visited: HashSet<Point>,
+/// A rope pulled around by its head, one step of the head per simulation step.
+pub struct Rope<'a> {
+    knots: Vec<Point<i32>>,
+    visited: HashSet<Point<i32>>,
+    movements: &'a [Direction],
+    moved: usize,
 }
 
-const MIN: i32 = -1;
-const MAX: i32 = 1;
-
-impl Rope {
-    fn new(size: u32) -> Self {
-        let start = Point { x: 0, y: 0 };
+impl<'a> Rope<'a> {
+    pub fn new(size: u32, movements: &'a [Direction]) -> Self {
+        let start = Point::origin();
         let mut visited = HashSet::new();
         visited.insert(start);
-        Rope { size, visited, elements: vec![start; size as usize] }
+        Rope { visited, knots: vec![start; size as usize], movements, moved: 0 }
     }
 
-    pub fn move_head(&mut self, movement: &Movement) {
-        let mut point: &mut Point = self.elements.get_mut(0).unwrap();
-        point.x += movement.dx;
-        point.y += movement.dy;
+    /// The number of positions the tail has visited so far.
+    pub fn visited(&self) -> usize {
+        self.visited.len()
     }
 
+    pub fn move_head(&mut self, direction: Direction) {
+        self.knots[0] = self.knots[0].step(direction);
+    }
+
+    /// Moves every knot that is no longer touching the knot before it one step closer.
     pub fn follow_head(&mut self) {
-        let i = self.size as usize;
-        for index in 1..i {
-            let head = self.elements.get(index - 1).unwrap();
-            let tail = self.elements.get(index).unwrap();
-            if let Some(delta) = self.calculate_position(head, tail) {
-                let tail = self.elements.get_mut(index).unwrap();
-                tail.x = delta.x;
-                tail.y = delta.y;
-            } else {
+        for index in 1..self.knots.len() {
+            let (head, tail) = (self.knots[index - 1], self.knots[index]);
+            if head.chebyshev(tail) <= 1 {
                 break;
             }
+            self.knots[index] += (head - tail).signum();
         }
     }
 
-    fn calculate_position(&self, head: &Point, tail: &Point) -> Option<Point> {
-        let dx = tail.x - head.x;
-        let dy = tail.y - head.y;
+    pub fn store_tail_position(&mut self) {
+        let tail = self.knots[self.knots.len() - 1];
+        self.visited.insert(tail);
+    }
 
-        if (dx == 2 || dx == -2) && (dy == 2 || dy == -2) {
-            Some(Point { x: head.x + dx.clamp(MIN, MAX), y: head.y + dy.clamp(MIN, MAX) })
-        } else if dx == 2 || dx == -2 {
-            Some(Point { x: head.x + dx.clamp(MIN, MAX), y: head.y })
-        } else if dy == 2 || dy == -2 {
-            Some(Point { x: head.x, y: head.y + dy.clamp(MIN, MAX) })
-        } else {
-            None
+    /// The area around the knots and the visited positions, with the head as 'H', the
+    /// other knots by their number, the start as 's' and the other positions visited by
+    /// the tail as '#'.
+    fn cells(&self) -> Grid<char> {
+        let points = self.knots.iter().chain(self.visited.iter()).copied();
+        let bounds = BoundingBox::of(points).unwrap_or(BoundingBox { min: Point::origin(), max: Point::origin() });
+        let mut cells = Vec::new();
+        for y in bounds.min.y..=bounds.max.y {
+            for x in bounds.min.x..=bounds.max.x {
+                let point = Point::new(x, y);
+                cells.push(match self.knots.iter().position(|knot| *knot == point) {
+                    Some(0) => 'H',
+                    Some(index) => char::from_digit(index as u32 % 36, 36).unwrap(),
+                    None if point == Point::origin() => 's',
+                    None if self.visited.contains(&point) => '#',
+                    None => '.',
+                });
+            }
         }
+        Grid::new(bounds.width() as usize, bounds.height() as usize, cells)
     }
 
-    pub fn store_tail_position(&mut self) {
-        let tail = self.elements[self.size as usize - 1];
-        self.visited.insert(tail);
+    /// The head red, the other knots yellow, the start blue and the trail of the tail grey.
+    pub fn picture(&self) -> Grid<Colour> {
+        self.cells().map(|cell| match cell {
+            'H' => Colour::RED,
+            's' => Colour::BLUE,
+            '#' => Colour::GREY,
+            '.' => Colour::BLACK,
+            _ => Colour::YELLOW,
+        })
     }
 }
 
+impl Simulation for Rope<'_> {
+    type Snapshot = String;
 
-pub fn solve() {
-    let file = "resources/day09.txt";
-    let lines = files::parse_lines_from(file);
-    let movements = parse_movements(lines);
+    fn step(&mut self) {
+        let Some(direction) = self.movements.get(self.moved) else { return };
+        self.move_head(*direction);
+        self.follow_head();
+        self.store_tail_position();
+        self.moved += 1;
+    }
 
-    let mut rope = Rope::new(2);
-    calculate(&movements, &mut rope);
+    fn is_done(&self) -> bool {
+        self.moved == self.movements.len()
+    }
 
-    let mut rope = Rope::new(10);
-    calculate(&movements, &mut rope);
+    /// Draws the knots and the trail of the tail as text.
+    fn snapshot(&self) -> String {
+        self.cells().render(|cell| *cell)
+    }
 }
 
 
-fn calculate(movements: &Vec<Movement>, rope: &mut Rope) {
-    for movement in movements {
-        rope.move_head(movement);
-        rope.follow_head();
-        rope.store_tail_position();
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Direction>;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter { name: "short_rope", description: "knots of the rope in part 1", puzzle: 2, example: 2 },
+        Parameter { name: "long_rope", description: "knots of the rope in part 2", puzzle: 10, example: 10 },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_movements(input)?)
+    }
+
+    fn part1(movements: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let rope = Rope::new(knots(parameters, "short_rope")?, movements);
+        Ok(calculate(rope).into())
+    }
+
+    fn part2(movements: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let rope = Rope::new(knots(parameters, "long_rope")?, movements);
+        Ok(calculate(rope).into())
+    }
+
+    fn pictures(movements: &Self::Input, part: Part, parameters: &Parameters) -> Result<Pictures, Error> {
+        let knots = knots(parameters, match part {
+            Part::One => "short_rope",
+            Part::Two => "long_rope",
+        })?;
+        Ok(render::frames(|| Rope::new(knots, movements), Rope::picture))
     }
-    println!("{}", rope.visited.len());
+}
+
+fn knots(parameters: &Parameters, name: &str) -> Result<u32, Error> {
+    parameters.at_least(name, 1, "a rope needs at least one knot")
+}
+
+fn calculate(rope: Rope) -> usize {
+    let mut driver = Driver::new(rope);
+    driver.run();
+    driver.simulation().visited()
 }
@@ -1,43 +1,109 @@
-use crate::files;
+use std::fmt;
 
-enum Operation {
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::parameters::Parameters;
+use crate::parse::{self, ParseError};
+use crate::render::{Colour, Pictures};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Answer, Part, Solution};
+
+pub enum Operation {
     NOOP,
     ADD(i32),
 }
 
-type Cycle = (i32, i32);  // (cycle, 💪 during)
+/// The value of the X register during a cycle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Signal {
+    pub cycle: i32,
+    pub strength: i32,
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cycle {}: X = {}", self.cycle, self.strength)
+    }
+}
 
-fn parse() -> Vec<Operation> {
-    files::parse_lines_from("resources/day10.txt")
-        .iter()
-        .map(|line| match line.split_at(4) {
-            ("noop", _) => Operation::NOOP,
-            ("addx", increment) => Operation::ADD(increment.trim().parse::<i32>().unwrap()),
-            (_, _) => unreachable!(),
+fn parse(input: &str) -> Result<Vec<Operation>, ParseError> {
+    input.lines()
+        .map(|line| match line.split_once(' ') {
+            None if line == "noop" => Ok(Operation::NOOP),
+            Some(("addx", increment)) => Ok(Operation::ADD(parse::number::<i32>(increment.trim())?)),
+            _ => Err(ParseError::new(line, "Expected 'noop' or 'addx <number>'")),
         })
         .collect()
 }
 
-fn parse_signal_history(operations: &Vec<Operation>) -> Vec<Cycle> {
-    let mut strength: i32 = 1;
-    let mut cycle: i32 = 1;
-    let mut signal_history: Vec<Cycle> = Vec::new();
-    for operation in operations {
+/// The CPU running the program, one cycle per step.
+pub struct Cpu<'a> {
+    operations: &'a [Operation],
+    executed: usize,
+    adding: bool,
+    signal: Signal,
+    history: Vec<Signal>,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(operations: &'a [Operation]) -> Self {
+        let signal = Signal { cycle: 1, strength: 1 };
+        Cpu { operations, executed: 0, adding: false, signal, history: Vec::new() }
+    }
+
+    /// The signals during all cycles so far.
+    pub fn history(&self) -> &[Signal] {
+        &self.history
+    }
+}
+
+impl Simulation for Cpu<'_> {
+    type Snapshot = Signal;
+
+    fn step(&mut self) {
+        let Some(operation) = self.operations.get(self.executed) else { return };
+        self.history.push(self.signal);
+        self.signal.cycle += 1;
         match operation {
-            Operation::NOOP => {
-                signal_history.push((cycle, strength));
-                cycle += 1;
-            }
+            Operation::NOOP => self.executed += 1,
+            Operation::ADD(_) if !self.adding => self.adding = true,
             Operation::ADD(increment) => {
-                signal_history.push((cycle, strength));
-                cycle += 1;
-                signal_history.push((cycle, strength));
-                cycle += 1;
-                strength += increment;
+                self.signal.strength += increment;
+                self.adding = false;
+                self.executed += 1;
             }
         }
     }
-    signal_history
+
+    fn is_done(&self) -> bool {
+        self.executed == self.operations.len()
+    }
+
+    /// The signal during the next cycle.
+    fn snapshot(&self) -> Signal {
+        self.signal
+    }
+}
+
+fn parse_signal_history(operations: &[Operation]) -> Vec<Signal> {
+    let mut driver = Driver::new(Cpu::new(operations));
+    driver.run();
+    driver.simulation().history().to_vec()
+}
+
+/// The CRT screen with lit pixels white, 40 pixels wide. A last row the program didn't
+/// finish drawing is filled up with dark pixels.
+pub fn picture(signal_history: &[Signal]) -> Grid<Colour> {
+    let mut sprite = Sprite::new(3);
+    let mut pixels = signal_history.iter()
+        .map(|signal| {
+            sprite.move_to(signal.strength);
+            if sprite.covers((signal.cycle - 1) % 40) { Colour::WHITE } else { Colour::BLACK }
+        })
+        .collect::<Vec<Colour>>();
+    let height = pixels.len().div_ceil(40);
+    pixels.resize(height * 40, Colour::BLACK);
+    Grid::new(40, height, pixels)
 }
 
 struct Sprite {
@@ -62,33 +128,45 @@ impl Sprite {
 }
 
 
-pub fn solve() {
-    let operations = parse();
-    let signal_history: Vec<_> = parse_signal_history(&operations);
+pub struct Day10;
 
-    let mut relevant_cycles = [20, 60, 100, 140, 180, 220];
-    let mut sum: i32 = 0;
-    for cycle in relevant_cycles {
-        let snapshot = signal_history[cycle - 1];
-        let cycle = snapshot.0;
-        let during = snapshot.1;
-        sum += cycle * during;
+impl Solution for Day10 {
+    type Input = Vec<Signal>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let operations = parse(input)?;
+        Ok(parse_signal_history(&operations))
     }
-    println!("Sum of relevant cycles: {}", sum);
 
-    println!("Screen output:");
+    fn part1(signal_history: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let relevant_cycles = [20, 60, 100, 140, 180, 220];
+        let mut sum: i32 = 0;
+        for cycle in relevant_cycles {
+            let signal = signal_history.get(cycle - 1)
+                .ok_or_else(|| Error::NoAnswer(format!("The program ends before cycle {}", cycle)))?;
+            sum += signal.cycle * signal.strength;
+        }
+        Ok(sum.into())
+    }
 
-    let mut sprite = Sprite::new(3);
-    for cycle in signal_history {
-        let index = cycle.0 - 1;
-        let position = index % 40;
-        let strength = cycle.1;
+    fn part2(signal_history: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let mut screen = String::new();
+        let mut sprite = Sprite::new(3);
+        for signal in signal_history {
+            let index = signal.cycle - 1;
+            let position = index % 40;
+            let strength = signal.strength;
 
-        if index % 40 == 0 { print!("\n") }
+            if index > 0 && index % 40 == 0 { screen.push('\n') }
 
-        sprite.move_to(strength);
-        print!("{}", if sprite.covers(position) { '#' } else { '.' });
+            sprite.move_to(strength);
+            screen.push(if sprite.covers(position) { '#' } else { '.' });
+        }
+        Ok(screen.into())
     }
 
-    print!("\n")
-}
\ No newline at end of file
+    /// The CRT screen, which part 2 reads the letters from.
+    fn pictures(signal_history: &Self::Input, _: Part, _: &Parameters) -> Result<Pictures, Error> {
+        Ok(vec![picture(signal_history)])
+    }
+}
@@ -1,15 +1,21 @@
 use std::collections::{BTreeMap, VecDeque};
 use std::fmt;
+use std::rc::Rc;
 
 use lazy_static::lazy_static;
-use regex::Regex;
 
-use crate::files;
+use crate::error::Error;
+use crate::parameters::{Parameter, Parameters};
+use crate::parse::{self, ParseError, Pattern};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Answer, Solution};
+use crate::trace::{self, Level};
 
-struct Monkey {
+#[derive(Clone)]
+pub struct Monkey {
     id: u64,
     items: VecDeque<u64>,
-    operation: Box<dyn Fn(u64) -> u64>,
+    operation: Rc<dyn Fn(u64) -> u64>,
     divider: u64,
     test_passed_throw_to_id: u64,
     test_failed_throw_to_id: u64,
@@ -25,64 +31,44 @@ impl fmt::Display for Monkey {
 }
 
 impl Monkey {
-    pub fn new_from(string: &str) -> Self {
-        let string = string.trim();
-        let id = {
-            lazy_static! { static ref RE: Regex = Regex::new(r"Monkey (?P<id>\d+):").unwrap(); }
-            let captures = RE.captures(string).unwrap();
-            let monkey_id: &str = &captures["id"];
-            monkey_id.parse().unwrap()
-        };
-        let items = {
-            lazy_static! { static ref RE: Regex = Regex::new(r"Starting items: (?P<items>.*?)\n").unwrap(); }
-            let captures = RE.captures(string).unwrap();
-            let monkey_id: &str = &captures["items"];
-            monkey_id.split(",")
-                .map(|id| id.trim().parse::<u64>().unwrap())
-                .collect::<VecDeque<u64>>()
-        };
-        let operation: Box<dyn Fn(u64) -> u64> = {
-            lazy_static! { static ref RE: Regex = Regex::new(r"Operation: new = old (?P<operation>[*+]) (?P<other>.*?)\n").unwrap(); }
-            let captures = RE.captures(string).unwrap();
-            let operation: &str = &captures["operation"];
-            let other: &str = &captures["other"];
-            match operation {
-                "+" => {
-                    let other = other.trim().parse::<u64>().unwrap();
-                    Box::new(move |x: u64| x + other)
-                }
-                "*" => {
-                    match other {
-                        "old" => Box::new(|x: u64| x * x),
-                        factor => {
-                            let other = factor.trim().parse::<u64>().unwrap();
-                            Box::new(move |old: u64| old * other)
-                        }
-                    }
-                }
-                _ => unreachable!()
+    pub fn new_from(block: &str) -> Result<Self, ParseError> {
+        lazy_static! {
+            static ref ID: Pattern = Pattern::new(r"Monkey (?P<id>\d+):", "Monkey <id>:");
+            static ref ITEMS: Pattern = Pattern::new(r"\s*Starting items: (?P<items>.*)", "Starting items: <items>");
+            static ref OPERATION: Pattern = Pattern::new(
+                r"\s*Operation: new = old (?P<operator>[*+]) (?P<operand>old|\d+)",
+                "Operation: new = old <operator> <operand>");
+            static ref TEST: Pattern = Pattern::new(r"\s*Test: divisible by (?P<divider>\d+)", "Test: divisible by <number>");
+            static ref IF_TRUE: Pattern = Pattern::new(r"\s*If true: throw to monkey (?P<id>\d+)", "If true: throw to monkey <id>");
+            static ref IF_FALSE: Pattern = Pattern::new(r"\s*If false: throw to monkey (?P<id>\d+)", "If false: throw to monkey <id>");
+        }
+        let mut lines = block.lines();
+        let mut next = |pattern: &'static Pattern| pattern.matches(lines.next().unwrap_or(&block[block.len()..]));
+
+        let id = next(&ID)?.number("id")?;
+        let items = next(&ITEMS)?.numbers::<u64>("items", ",")?.into();
+        let operation = next(&OPERATION)?;
+        let operation: Rc<dyn Fn(u64) -> u64> = match (operation.str("operator"), operation.str("operand")) {
+            ("*", "old") => Rc::new(|old: u64| old * old),
+            ("+", "old") => Rc::new(|old: u64| old + old),
+            ("*", _) => {
+                let factor = operation.number::<u64>("operand")?;
+                Rc::new(move |old: u64| old * factor)
+            }
+            _ => {
+                let summand = operation.number::<u64>("operand")?;
+                Rc::new(move |old: u64| old + summand)
             }
         };
-        let divider = {
-            lazy_static! { static ref RE: Regex = Regex::new(r"Test: divisible by (?P<divider>.*?)\n").unwrap(); }
-            let captures = RE.captures(string).unwrap();
-            let divider: &str = &captures["divider"];
-            divider.trim().parse::<u64>().unwrap()
-        };
-        let test_passed_throw_to_id = {
-            lazy_static! { static ref RE: Regex = Regex::new(r"If true: throw to monkey (?P<id>.*?)\n").unwrap(); }
-            let captures = RE.captures(string).unwrap();
-            let id: &str = &captures["id"];
-            id.trim().parse::<u64>().unwrap()
-        };
-        let test_failed_throw_to_id = {
-            lazy_static! { static ref RE: Regex = Regex::new(r"If false: throw to monkey (?P<id>.*?)$").unwrap(); }
-            let captures = RE.captures(string).unwrap();
-            let id: &str = &captures["id"];
-            id.trim().parse::<u64>().unwrap()
-        };
+        let test = next(&TEST)?;
+        let divider = test.number("divider")?;
+        if divider == 0 {
+            return Err(ParseError::new(test.str("divider"), "Expected a divisor other than 0"));
+        }
+        let test_passed_throw_to_id = next(&IF_TRUE)?.number("id")?;
+        let test_failed_throw_to_id = next(&IF_FALSE)?.number("id")?;
         let inspect_counter = 0;
-        Monkey { id, items, operation, divider, test_passed_throw_to_id, test_failed_throw_to_id, inspect_counter }
+        Ok(Monkey { id, items, operation, divider, test_passed_throw_to_id, test_failed_throw_to_id, inspect_counter })
     }
 
     /// Monkey inspects the item until he gets bored. Returns a tuple with new worry_level and receiver.
@@ -93,7 +79,7 @@ impl Monkey {
         let worry_level: u64 = (self.operation)(worry_level);
         let worry_level: u64 = worry_level / relief_factor;
 
-        if worry_level % self.divider == 0 {
+        if worry_level.is_multiple_of(self.divider) {
             (worry_level, self.test_passed_throw_to_id)
         } else {
             (worry_level, self.test_failed_throw_to_id)
@@ -101,56 +87,144 @@ impl Monkey {
     }
 }
 
-pub fn solve() {
-    let file = "resources/day11.txt";
-    let string = files::parse_string_from(file).unwrap();
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter { name: "part1_rounds", description: "rounds played in part 1", puzzle: 20, example: 20 },
+        Parameter { name: "part1_relief", description: "worry level divisor in part 1", puzzle: 3, example: 3 },
+        Parameter { name: "part2_rounds", description: "rounds played in part 2", puzzle: 10_000, example: 10_000 },
+        Parameter { name: "part2_relief", description: "worry level divisor in part 2", puzzle: 1, example: 1 },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let blocks: Vec<&str> = parse::blocks(input).collect();
+        let monkeys = blocks.iter()
+            .map(|block| Monkey::new_from(block))
+            .collect::<Result<Vec<Monkey>, ParseError>>()?;
+
+        // The first line of a monkey names it.
+        for (index, (block, monkey)) in blocks.iter().zip(&monkeys).enumerate() {
+            if monkeys[..index].iter().any(|other| other.id == monkey.id) {
+                let line = block.lines().next().unwrap_or(block);
+                let id = line.trim_end().trim_end_matches(':').rsplit(' ').next().unwrap_or(line);
+                return Err(ParseError::new(id, format!("Expected a new monkey, there is a monkey {} already", monkey.id)).into());
+            }
+        }
+
+        // The fifth and sixth line of a monkey name whom it throws to.
+        for (block, monkey) in blocks.iter().zip(&monkeys) {
+            let targets = [monkey.test_passed_throw_to_id, monkey.test_failed_throw_to_id];
+            for (line, target) in block.lines().skip(4).zip(targets) {
+                if !monkeys.iter().any(|other| other.id == target) {
+                    let id = line.trim_end().rsplit(' ').next().unwrap_or(line);
+                    return Err(ParseError::new(id, format!("Expected a monkey to throw to, there is no monkey {}", target)).into());
+                }
+            }
+        }
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let relief_factor = relief(parameters, "part1_relief")?;
+        let rounds = parameters.get("part1_rounds")?;
+        Ok(calculate_monkey_business(monkeys, relief_factor, rounds)?.into())
+    }
 
-    let relief_factor = 3;
-    let rounds = 20;
-    calculate_monkey_business(&string, relief_factor, rounds);
+    fn part2(monkeys: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let relief_factor = relief(parameters, "part2_relief")?;
+        let rounds = parameters.get("part2_rounds")?;
+        Ok(calculate_monkey_business(monkeys, relief_factor, rounds)?.into())
+    }
+}
 
-    let relief_factor = 1;
-    let rounds = 10000;
-    calculate_monkey_business(&string, relief_factor, rounds);
+fn relief(parameters: &Parameters, name: &str) -> Result<u64, Error> {
+    parameters.at_least(name, 1, "the worry level cannot be divided by zero")
 }
 
-fn calculate_monkey_business(string: &String, relief_factor: u64, rounds: u64) {
-    let monkeys = string.split("\n\n")
-        .map(|line| Monkey::new_from(line))
-        .map(|monkey| (monkey.id, monkey))
-        .collect::<Vec<(u64, Monkey)>>();
+/// The monkeys playing keep away, one round per step.
+pub struct Circus {
+    monkeys: BTreeMap<u64, Monkey>,
+    relief_factor: u64,
+    prime_lcm: u64,
+    rounds: u64,
+    played: u64,
+}
 
-    let prime_lcm: u64 = monkeys.iter()
-        .map(|it| it.1.divider)
-        .product();
+impl Circus {
+    /// Fails if the worry levels can't be kept small, because the divisors multiply to more
+    /// than fits a `u64`.
+    pub fn new(monkeys: &[Monkey], relief_factor: u64, rounds: u64) -> Result<Self, Error> {
+        let prime_lcm = monkeys.iter()
+            .try_fold(1u64, |product, monkey| product.checked_mul(monkey.divider))
+            .ok_or_else(|| Error::no_answer("The divisors of the monkeys multiply to more than a u64 holds"))?;
+        let monkeys = monkeys.iter()
+            .map(|monkey| (monkey.id, monkey.clone()))
+            .collect();
+        Ok(Circus { monkeys, relief_factor, prime_lcm, rounds, played: 0 })
+    }
+
+    /// The product of the two highest numbers of inspected items.
+    pub fn monkey_business(&self) -> u64 {
+        let mut inspect_counters = self.monkeys.values()
+            .map(|monkey| monkey.inspect_counter)
+            .collect::<Vec<u64>>();
 
-    let mut circus: BTreeMap<u64, Monkey> = monkeys.into_iter().collect();
-    let keys = circus.keys().cloned().collect::<Vec<u64>>();
+        inspect_counters.sort();
 
-    for _ in 0..rounds {
+        inspect_counters.iter()
+            .rev()
+            .take(2)
+            .product()
+    }
+}
+
+impl Simulation for Circus {
+    type Snapshot = String;
+
+    fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+        let keys = self.monkeys.keys().cloned().collect::<Vec<u64>>();
         for key in keys.iter() {
-            let monkey = circus.get_mut(&key).unwrap();
-            let mut items = monkey.items.clone();
-            monkey.items.clear();
+            let monkey = self.monkeys.get_mut(key).unwrap();
+            let mut items = std::mem::take(&mut monkey.items);
             while let Some(current_item) = items.pop_front() {
-                let monkey = circus.get_mut(&key).unwrap();
-                let (new_item, next_owner) = monkey.inspect(current_item, relief_factor, &prime_lcm);
-                let next_owner = circus.get_mut(&next_owner).unwrap();
+                let monkey = self.monkeys.get_mut(key).unwrap();
+                let (new_item, next_owner) = monkey.inspect(current_item, self.relief_factor, &self.prime_lcm);
+                trace::event(Level::TRACE, "throw", &[
+                    ("round", (self.played + 1).into()),
+                    ("from", (*key).into()),
+                    ("to", next_owner.into()),
+                    ("worry", new_item.into()),
+                ]);
+                let next_owner = self.monkeys.get_mut(&next_owner).unwrap();
                 next_owner.items.push_back(new_item);
             }
         }
+        self.played += 1;
     }
 
-    let mut inspect_counters = circus.values()
-        .map(|monkey| monkey.inspect_counter)
-        .collect::<Vec<u64>>();
-
-    inspect_counters.sort();
+    fn is_done(&self) -> bool {
+        self.played == self.rounds
+    }
 
-    let monkey_business: u64 = inspect_counters.iter()
-        .rev()
-        .take(2)
-        .product();
+    /// The items every monkey holds and how many items it inspected so far.
+    fn snapshot(&self) -> String {
+        let mut text = format!("After round {}:\n", self.played);
+        for monkey in self.monkeys.values() {
+            let items = monkey.items.iter().map(u64::to_string).collect::<Vec<String>>();
+            text.push_str(&format!("Monkey {}: {} (inspected {})\n", monkey.id, items.join(", "), monkey.inspect_counter));
+        }
+        text
+    }
+}
 
-    println!("Monkey Business {}", monkey_business);
+fn calculate_monkey_business(monkeys: &[Monkey], relief_factor: u64, rounds: u64) -> Result<u64, Error> {
+    let mut driver = Driver::new(Circus::new(monkeys, relief_factor, rounds)?);
+    driver.run();
+    Ok(driver.simulation().monkey_business())
 }
@@ -1,158 +1,126 @@
-use std::collections::{HashSet, VecDeque};
 use std::ffi::c_uint;
 
-use crate::files;
-
-#[derive(Debug, Eq, PartialEq, Hash)]
-struct Item {
-    character: char,
-    column: usize,
-    row: usize,
-}
-
-impl Item {
-    pub fn new(character: char, column: usize, row: usize) -> Self {
-        Item { character, column, row }
-    }
-
-    pub fn elevation(&self) -> usize {
-        let character = match self.character {
-            'S' => 'a',
-            'E' => 'z',
-            c => c,
-        };
-        (character as c_uint - 'a' as c_uint) as usize
-    }
-
-    pub fn can_visit(&self, other: &Item) -> bool {
-        let mine = self.elevation() as i32;
-        let theirs = other.elevation() as i32;
-        theirs - mine <= 1
-    }
+use crate::error::Error;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::parameters::Parameters;
+use crate::parse::ParseError;
+use crate::render::{Colour, Pictures};
+use crate::search::{self, Found, Graph};
+use crate::solution::{Answer, Part, Solution};
+
+/// The elevation of a square, 'S' being at the lowest and 'E' at the highest elevation.
+pub fn elevation(square: char) -> i32 {
+    let square = match square {
+        'S' => 'a',
+        'E' => 'z',
+        c => c,
+    };
+    (square as c_uint - 'a' as c_uint) as i32
 }
 
-struct Map {
-    width: usize,
-    height: usize,
-    elements: Vec<Item>,
+pub struct Map {
+    squares: Grid<char>,
 }
 
 impl Map {
-    pub fn from(string: &String) -> Self {
-        let width = string.find("\n").unwrap();
-        let height = string.trim().len() / width;
-        let string = string.chars()
-            .filter(|c| !c.is_whitespace())
-            .collect::<String>();
-        let mut elements: Vec<Item> = vec![];
-        for row in 0..height {
-            for column in 0..width {
-                let index: usize = column + row * width;
-                let char = string.as_bytes()[index] as char;
-                let item = Item::new(char, column, row);
-                elements.push(item);
-            }
-        }
-        Map { width, height, elements }
+    pub fn from(string: &str) -> Result<Self, ParseError> {
+        let squares = Grid::parse(string, |square| match square {
+            'a'..='z' | 'S' | 'E' => Ok(square),
+            _ => Err("Expected an elevation 'a'-'z', 'S' or 'E'".to_string()),
+        })?;
+        Ok(Map { squares })
     }
 
-    fn item(&self, column: usize, row: usize) -> Option<&Item> {
-        let index: usize = column + row * self.width;
-        self.elements.get(index)
+    /// The neighbouring squares that are at most one higher than the given square.
+    pub fn climbable_from(&self, position: Point<usize>) -> impl Iterator<Item=Point<usize>> + '_ {
+        let current = elevation(self.squares[position]);
+        self.squares.neighbours(position)
+            .filter(move |neighbour| elevation(self.squares[*neighbour]) - current <= 1)
     }
 
-    fn item_left_of(&self, item: &Item) -> Option<&Item> {
-        if item.column == 0 {
-            None
-        } else {
-            self.item(item.column - 1, item.row)
-        }
+    pub fn find(&self, char: char) -> Option<Point<usize>> {
+        self.squares.position(|square| *square == char)
     }
 
-    fn item_right_of(&self, item: &Item) -> Option<&Item> {
-        if item.column >= self.width {
-            None
-        } else {
-            self.item(item.column + 1, item.row)
+    /// Low squares are blue, high squares white, the start and the end yellow and the
+    /// squares of the path red.
+    pub fn picture(&self, path: &[Point<usize>]) -> Grid<Colour> {
+        let mut picture = self.squares.map(|square| match square {
+            'S' | 'E' => Colour::YELLOW,
+            square => Colour::BLUE.blend(Colour::WHITE, elevation(*square) as f64 / 25.0),
+        });
+        for position in path {
+            if !matches!(self.squares[*position], 'S' | 'E') {
+                picture[*position] = Colour::RED;
+            }
         }
+        picture
     }
 
-    fn item_above(&self, item: &Item) -> Option<&Item> {
-        if item.row == 0 {
-            None
-        } else {
-            self.item(item.column, item.row - 1)
-        }
+    pub fn find_all(&self, char: char) -> Vec<Point<usize>> {
+        self.squares.iter()
+            .filter(|(_, square)| **square == char)
+            .map(|(position, _)| position)
+            .collect::<Vec<Point<usize>>>()
     }
 
-    fn item_below(&self, item: &Item) -> Option<&Item> {
-        if item.row >= self.height {
-            None
-        } else {
-            self.item(item.column, item.row + 1)
-        }
+    /// The fewest steps from any of the starts to the goal, searching from all starts at once.
+    pub fn fewest_steps(&self, starts: Vec<Point<usize>>, goal: Point<usize>) -> Option<u64> {
+        search::bfs(self, starts, |position| *position == goal).map(|found| found.cost)
     }
 
-    pub fn item_neighbours_of(&self, item: &Item) -> Vec<&Item> {
-        let neighbours = [
-            self.item_left_of(item), self.item_right_of(item),
-            self.item_above(item), self.item_below(item),
-        ];
-        neighbours.iter().filter_map(|item| *item).collect::<Vec<&Item>>()
+    /// Slow but obviously correct version of [`Map::fewest_steps`], which searches from
+    /// every start on its own.
+    pub fn fewest_steps_naive(&self, starts: Vec<Point<usize>>, goal: Point<usize>) -> Option<u64> {
+        starts.into_iter()
+            .filter_map(|start| search::bfs(self, [start], |position| *position == goal))
+            .map(|found| found.cost)
+            .min()
     }
+}
 
-    pub fn find(&self, char: char) -> Option<&Item> {
-        self.elements.iter().find(|item| item.character == char)
-    }
+impl Graph for Map {
+    type Node = Point<usize>;
 
-    pub fn find_all(&self, char: char) -> Vec<&Item> {
-        self.elements.iter().filter(|item| item.character == char).collect::<Vec<&Item>>()
+    fn neighbours(&self, position: &Point<usize>) -> Vec<(Point<usize>, u64)> {
+        self.climbable_from(*position).map(|neighbour| (neighbour, 1)).collect()
     }
 }
 
-pub fn solve() {
-    let file = "resources/day12.txt";
-    let string = files::parse_string_from(file).unwrap();
+pub struct Day12;
 
-    let map = Map::from(&string);
-    let start = map.find('S').unwrap();
-    let end = map.find('E').unwrap();
+impl Solution for Day12 {
+    type Input = Map;
 
-    let min_distance_start = climb_hill(&map, start, end);
-    println!("Part 1: {}", min_distance_start);
-
-    let min_distance_any_a = map.find_all('a').iter()
-        .map(|start| climb_hill(&map, start, end)).min().unwrap();
-
-    println!("Part 2: {}", min_distance_any_a);
-}
-
-
-fn climb_hill(map: &Map, start: &Item, end: &Item) -> usize {
-    let mut min_distance = usize::MAX;
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(Map::from(input)?)
+    }
 
-    let mut visited: HashSet<&Item> = HashSet::new();
-    let mut next: VecDeque<(u32, &Item)> = VecDeque::new();
-    next.push_front((0, start));
+    fn part1(map: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        Ok(shortest_path(map, Part::One)?.cost.into())
+    }
 
-    while !next.is_empty() {
-        let (distance, current) = next.pop_front().unwrap();
+    fn part2(map: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        Ok(shortest_path(map, Part::Two)?.cost.into())
+    }
 
-        if current == end {
-            min_distance = distance as usize;
-        }
+    fn pictures(map: &Self::Input, part: Part, _: &Parameters) -> Result<Pictures, Error> {
+        Ok(vec![map.picture(&shortest_path(map, part)?.path())])
+    }
+}
 
-        for neighbor in map.item_neighbours_of(current) {
-            if visited.contains(neighbor) {
-                continue;
-            }
-            if current.can_visit(neighbor) {
-                let distance = distance + 1;
-                next.push_back((distance, neighbor));
-                visited.insert(neighbor);
-            }
+/// The shortest path to the end, from the start in part 1 and from any square of elevation
+/// 'a' in part 2.
+fn shortest_path(map: &Map, part: Part) -> Result<Found<Point<usize>>, Error> {
+    let end = map.find('E').ok_or_else(|| Error::no_answer("The map has no end 'E'"))?;
+    match part {
+        Part::One => {
+            let start = map.find('S').ok_or_else(|| Error::no_answer("The map has no start 'S'"))?;
+            search::bfs(map, [start], |position| *position == end)
+                .ok_or_else(|| Error::no_answer("The end 'E' cannot be reached from the start 'S'"))
         }
+        Part::Two => search::bfs(map, map.find_all('a'), |position| *position == end)
+            .ok_or_else(|| Error::no_answer("The end 'E' cannot be reached from any square of elevation 'a'")),
     }
-
-    min_distance
-}
\ No newline at end of file
+}
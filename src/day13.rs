@@ -2,24 +2,35 @@ use std::cmp;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 
-use crate::files;
+use crate::error::Error;
+use crate::parameters::Parameters;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 
 const RADIX: u32 = 10;
 
 
+/// A packet: an integer or a list of packets.
 #[derive(Clone, Debug, Eq, PartialEq)]
-enum Value {
+pub enum Value {
     INT(u32),
     LIST(Vec<Value>),
 }
 
 impl Value {
-    pub fn from(string: &str) -> Self {
+    pub fn parse(string: &str) -> Result<Self, ParseError> {
         let mut parser = ValueParser::from(string);
-        parser.parse()
+        parser.parse().map_err(|(position, message)| {
+            let (index, character) = string.char_indices()
+                .nth(position as usize)
+                .unwrap_or((string.len(), ' '));
+            let end = cmp::min(index + character.len_utf8(), string.len());
+            ParseError::new(&string[index..end], message)
+        })
     }
 
-    fn compare(left: &Value, right: &Value) -> Option<bool> {
+    /// Whether `left` comes before `right`, or `None` if the order is undecided.
+    pub fn compare(left: &Value, right: &Value) -> Option<bool> {
         match left {
             Value::INT(left) => match right {
                 Value::INT(right) => Value::comp_int_int(left, right),
@@ -52,7 +63,7 @@ impl Value {
         Value::compare(&left, &right)
     }
 
-    fn comp_list_list(left: &Vec<Value>, right: &Vec<Value>) -> Option<bool> {
+    fn comp_list_list(left: &[Value], right: &[Value]) -> Option<bool> {
         let longest = cmp::max(left.len(), right.len());
         for index in 0..longest {
             if let Some(right) = right.get(index) {
@@ -90,55 +101,71 @@ impl Ord for Value {
 }
 
 
-struct ValuePair {
+pub struct ValuePair {
     left: Value,
     right: Value,
 }
 
 impl ValuePair {
-    pub fn from(string: &str) -> Self {
-        let mut split = string.splitn(2, "\n");
-        Self {
-            left: Value::from(split.next().unwrap()),
-            right: Value::from(split.next().unwrap()),
-        }
+    pub fn from(string: &str) -> Result<Self, ParseError> {
+        let (left, right) = parse::split_once(string, "\n")?;
+        Ok(Self {
+            left: Value::parse(left)?,
+            right: Value::parse(right)?,
+        })
     }
 
-    pub fn is_ordered(&self) -> bool {
-        Value::compare(&self.left, &self.right).unwrap()
+    /// Whether the left packet comes first. Fails if the packets are equal, naming the pair
+    /// by its `number`.
+    pub fn is_ordered(&self, number: usize) -> Result<bool, Error> {
+        Value::compare(&self.left, &self.right)
+            .ok_or_else(|| Error::no_answer(&format!("The packets of pair {} are equal, so they have no order", number)))
     }
 }
 
 
+/// Position (in characters) and description of a syntax error in a packet.
+type Failure = (u32, &'static str);
+
 struct ValueParser {
     characters: VecDeque<char>,
     consumed: u32,
+    offset: u32,
+    nested: bool,
 }
 
 impl ValueParser {
     pub fn from(string: &str) -> Self {
-        Self { characters: string.chars().collect::<VecDeque<char>>(), consumed: 0 }
+        Self { characters: string.chars().collect::<VecDeque<char>>(), consumed: 0, offset: 0, nested: false }
     }
 
-    fn new(chars: VecDeque<char>) -> Self {
-        Self { characters: chars, consumed: 0 }
+    fn new(chars: VecDeque<char>, offset: u32) -> Self {
+        Self { characters: chars, consumed: 0, offset, nested: true }
     }
 
-    pub fn parse(&mut self) -> Value {
+    pub fn parse(&mut self) -> Result<Value, Failure> {
         let mut values: Vec<Value> = vec![];
-        while let Some(next) = self.next() {
-            if next.is_digit(RADIX) {
-                values.push(self.next_number(next))
-            } else if next == '[' {
-                values.push(self.next_list())
-            } else if next == ']' {
-                break;
+        loop {
+            match self.next() {
+                Some(next) if next.is_digit(RADIX) => values.push(self.next_number(next)?),
+                Some('[') => values.push(self.next_list()?),
+                Some(']') if self.nested => break,
+                Some(',') => {}
+                Some(_) => return Err((self.position(), "Unexpected character in packet")),
+                None if self.nested => return Err((self.offset - 1, "List is never closed")),
+                None => break,
             }
         }
-        return Value::LIST(values);
+        Ok(Value::LIST(values))
+    }
+
+    /// Position of the last consumed character within the whole packet.
+    fn position(&self) -> u32 {
+        self.offset + self.consumed - 1
     }
 
-    fn next_number(&mut self, beginning: char) -> Value {
+    fn next_number(&mut self, beginning: char) -> Result<Value, Failure> {
+        let start = self.position();
         let mut number: Vec<char> = vec![beginning];
         while let Some(next_next) = self.peek() {
             if next_next.is_digit(RADIX) {
@@ -147,15 +174,16 @@ impl ValueParser {
                 break;
             }
         }
-        let number = number.into_iter().collect::<String>().parse::<u32>().unwrap();
-        Value::INT(number)
+        let number = number.into_iter().collect::<String>().parse::<u32>()
+            .map_err(|_| (start, "Number is too large"))?;
+        Ok(Value::INT(number))
     }
 
-    fn next_list(&mut self) -> Value {
-        let mut parser = ValueParser::new(self.characters.clone());
-        let value = parser.parse();
+    fn next_list(&mut self) -> Result<Value, Failure> {
+        let mut parser = ValueParser::new(self.characters.clone(), self.offset + self.consumed);
+        let value = parser.parse()?;
         self.skip(parser.consumed);
-        value
+        Ok(value)
     }
 
     fn skip(&mut self, characters: u32) {
@@ -168,53 +196,52 @@ impl ValueParser {
     }
 
     fn peek(&self) -> Option<&char> {
-        self.characters.get(0)
+        self.characters.front()
     }
 }
 
 
-pub fn solve() {
-    let file = "resources/day13.txt";
-    let string = files::parse_string_from(file).unwrap();
+pub struct Day13;
 
-    part_1(&string);
-    part_2(&string);
-}
+impl Solution for Day13 {
+    type Input = Vec<ValuePair>;
 
-fn part_1(string: &String) {
-    let value_pairs = string
-        .trim()
-        .split("\n\n")
-        .map(|double_line| ValuePair::from(double_line))
-        .collect::<Vec<ValuePair>>();
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let value_pairs = parse::blocks(input)
+            .map(ValuePair::from)
+            .collect::<Result<Vec<ValuePair>, ParseError>>()?;
+        Ok(value_pairs)
+    }
 
-    let mut index_sum = 0;
+    fn part1(value_pairs: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let mut index_sum: usize = 0;
 
-    for (index, value_pair) in value_pairs.iter().enumerate() {
-        if value_pair.is_ordered() {
-            index_sum += index + 1;
+        for (index, value_pair) in value_pairs.iter().enumerate() {
+            if value_pair.is_ordered(index + 1)? {
+                index_sum += index + 1;
+            }
         }
-    }
 
-    println!("Sum of indices: {}", index_sum)
-}
+        Ok(index_sum.into())
+    }
 
-fn part_2(string: &String) {
-    let mut values = string.trim()
-        .split("\n")
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| Value::from(line))
-        .collect::<Vec<Value>>();
+    fn part2(value_pairs: &Self::Input, _: &Parameters) -> Result<Answer, Error> {
+        let mut values = value_pairs.iter()
+            .flat_map(|pair| [pair.left.clone(), pair.right.clone()])
+            .collect::<Vec<Value>>();
 
-    let marker_1 = Value::from("[[2]]");
-    let marker_2 = Value::from("[[6]]");
-    let markers = vec![marker_1.clone(), marker_2.clone()];
+        let marker_1 = Value::parse("[[2]]")?;
+        let marker_2 = Value::parse("[[6]]")?;
+        let markers = vec![marker_1.clone(), marker_2.clone()];
 
-    values.extend(markers);
-    values.sort();
+        values.extend(markers);
+        values.sort();
 
-    let idx_marker_1 = values.iter().position(|r| r == &marker_1).unwrap() + 1;
-    let idx_marker_2 = values.iter().position(|r| r == &marker_2).unwrap() + 1;
+        let idx_marker_1 = values.iter().position(|r| r == &marker_1)
+            .ok_or_else(|| Error::no_answer("The divider packet [[2]] is missing"))? + 1;
+        let idx_marker_2 = values.iter().position(|r| r == &marker_2)
+            .ok_or_else(|| Error::no_answer("The divider packet [[6]] is missing"))? + 1;
 
-    println!("Decoder key: {}", idx_marker_1 * idx_marker_2)
-}
\ No newline at end of file
+        Ok((idx_marker_1 * idx_marker_2).into())
+    }
+}
@@ -2,71 +2,78 @@ use std::cmp;
 
 use itertools::Itertools;
 
-use crate::files;
+use crate::error::Error;
+use crate::geometry::{self, BoundingBox, Point};
+use crate::grid::Grid;
+use crate::parameters::{Parameter, Parameters};
+use crate::parse::{self, ParseError};
+use crate::render::{self, Colour, Pictures};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Answer, Part, Solution};
+use crate::trace::{self, Level};
 
 #[derive(Copy, Clone, Debug)]
-enum Element {
+pub enum Element {
     ROCK,
     SAND,
     AIR,
 }
 
 
-#[derive(Copy, Clone, Debug)]
-struct Coordinate {
-    x: usize,
-    y: usize,
-}
+pub type Coordinate = Point<usize>;
 
-impl Coordinate {
-    pub fn new(x: usize, y: usize) -> Self {
-        Self { x, y }
-    }
+/// Where the sand enters the cave.
+pub const SAND_SOURCE: Coordinate = Point::new(500, 0);
 
-    pub fn from_string(string: &str) -> Self {
-        let mut split = string.split(",");
-        let x = split.next().unwrap().parse::<usize>().unwrap();
-        let y = split.next().unwrap().parse::<usize>().unwrap();
-        Self { x, y }
-    }
-
-    pub fn between(c1: &Coordinate, c2: &Coordinate) -> Vec<Coordinate> {
-        let mut coordinates: Vec<Coordinate> = Vec::new();
-        if c1.is_horizontal_to(c2) {
-            let min = cmp::min(c1.x, c2.x);
-            let max = cmp::max(c1.x, c2.x);
-            for x in min..=max {
-                coordinates.push(Coordinate { x, y: c1.y })
-            }
-        } else {
-            let min = cmp::min(c1.y, c2.y);
-            let max = cmp::max(c1.y, c2.y);
-            for y in min..=max {
-                coordinates.push(Coordinate { x: c1.x, y })
-            }
-        }
-        coordinates
-    }
-
-    fn is_horizontal_to(&self, other_coordinate: &Coordinate) -> bool {
-        self.y == other_coordinate.y
-    }
+/// Parses a coordinate written as `x,y`.
+pub fn parse_coordinate(string: &str) -> Result<Coordinate, ParseError> {
+    let (x, y) = parse::split_once(string, ",")?;
+    let x = parse::number::<usize>(x)?;
+    let y = parse::number::<usize>(y)?;
+    Ok(Coordinate::new(x, y))
 }
 
 
-struct Cave {
-    width: usize,
-    height: usize,
-    elements: Vec<Element>,
+/// A cave filling with sand, one unit of sand per simulation step.
+#[derive(Clone)]
+pub struct Cave {
+    elements: Grid<Element>,
     sand_consumed: usize,
     deepest_rock: usize,
+    full: bool,
+    /// The area of the rocks and the sand, without the floor.
+    drawn: BoundingBox<usize>,
 }
 
 impl Cave {
     pub fn new(width: usize, height: usize) -> Self {
-        let mut elements = Vec::new();
-        elements.resize(width * height, Element::AIR);
-        Self { width, height, elements, sand_consumed: 0, deepest_rock: 0 }
+        let elements = Grid::filled(width, height, Element::AIR);
+        let drawn = BoundingBox { min: SAND_SOURCE, max: SAND_SOURCE };
+        Self { elements, sand_consumed: 0, deepest_rock: 0, full: false, drawn }
+    }
+
+    pub fn sand_consumed(&self) -> usize {
+        self.sand_consumed
+    }
+
+    pub fn deepest_rock(&self) -> usize {
+        self.deepest_rock
+    }
+
+    /// The area of the rocks and the sand with rock brown, sand yellow and air black.
+    pub fn picture(&self) -> Grid<Colour> {
+        let (min, max) = (self.drawn.min, self.drawn.max);
+        let mut cells = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                cells.push(match self.element_get(x, y) {
+                    Element::ROCK => Colour::BROWN,
+                    Element::SAND => Colour::YELLOW,
+                    Element::AIR => Colour::BLACK,
+                });
+            }
+        }
+        Grid::new(self.drawn.width(), self.drawn.height(), cells)
     }
 
     fn put_element_at(&mut self, x: usize, y: usize, element: Element) {
@@ -81,23 +88,30 @@ impl Cave {
     }
 
     fn element_get(&self, x: usize, y: usize) -> &Element {
-        let index: usize = x + y * self.width;
-        self.elements.get(index).unwrap()
+        &self.elements[Coordinate::new(x, y)]
     }
 
     fn element_set(&mut self, x: usize, y: usize, element: Element) {
-        let index: usize = x + y * self.width;
-        self.elements[index] = element;
+        self.elements[Coordinate::new(x, y)] = element;
     }
 
     pub fn put_rocks_between(&mut self, c1: Coordinate, c2: Coordinate) {
         let rock = Element::ROCK;
-        for coordinate in Coordinate::between(&c1, &c2).iter() {
+        for coordinate in geometry::line(c1, c2) {
             self.put_element_at(coordinate.x, coordinate.y, rock);
             self.deepest_rock = cmp::max(self.deepest_rock, coordinate.y);
+            self.drawn.include(coordinate);
         }
     }
 
+    /// Puts rock across the whole width of the cave.
+    pub fn put_floor_at(&mut self, y: usize) {
+        let width = self.elements.width();
+        let drawn = self.drawn;
+        self.put_rocks_between(Coordinate::new(0, y), Coordinate::new(width - 1, y));
+        self.drawn = BoundingBox::of([drawn.min, drawn.max, Coordinate::new(SAND_SOURCE.x, y)]).unwrap();
+    }
+
     pub fn drop_sand_at(&mut self, sand_entry: Coordinate) -> bool {
         let mut x = sand_entry.x;
         let mut y = sand_entry.y;
@@ -120,16 +134,12 @@ impl Cave {
                 continue;
             }
             self.put_sand_at(x, y);
-            return if x == sand_entry.x && y == sand_entry.y {
-                false
-            } else {
-                true
-            }
+            return x != sand_entry.x || y != sand_entry.y;
         }
     }
 
     fn dropped_out(&self, y: usize) -> bool {
-        y >= self.height - 1
+        y >= self.elements.height() - 1
     }
 
     fn element_below_is_air(&self, x: usize, y: usize) -> bool {
@@ -147,51 +157,129 @@ impl Cave {
     fn put_sand_at(&mut self, x: usize, y: usize) {
         self.put_element_at(x, y, Element::SAND);
         self.sand_consumed += 1;
+        trace::event(Level::TRACE, "settle", &[("grain", self.sand_consumed.into()), ("x", x.into()), ("y", y.into())]);
+        self.drawn.include(Coordinate::new(x, y));
     }
 }
 
+impl Simulation for Cave {
+    type Snapshot = String;
+
+    /// Drops one unit of sand from the source. The cave is full once sand falls out of it
+    /// or the source is blocked.
+    fn step(&mut self) {
+        if !self.full {
+            self.full = !self.drop_sand_at(SAND_SOURCE);
+        }
+    }
 
-fn construct_cave(width: usize, height: usize) -> Cave {
+    fn is_done(&self) -> bool {
+        self.full
+    }
+
+    /// Draws rock as '#', sand as 'o' and air as '.', limited to the area of the rocks and
+    /// the sand.
+    fn snapshot(&self) -> String {
+        let mut text = String::new();
+        for y in self.drawn.min.y..=self.drawn.max.y {
+            for x in self.drawn.min.x..=self.drawn.max.x {
+                text.push(match self.element_get(x, y) {
+                    Element::ROCK => '#',
+                    Element::SAND => 'o',
+                    Element::AIR => '.',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+
+pub type RockPath = Vec<Coordinate>;
+
+pub fn construct_cave(rock_paths: &[RockPath], width: usize, height: usize) -> Cave {
     let mut cave = Cave::new(width, height);
-    let file = "resources/day14.txt";
-    let lines = files::parse_lines_from(file);
-    for line in lines {
-        let coordinates = line.split(" -> ")
-            .map(|s| Coordinate::from_string(s))
-            .into_iter();
-        for (c1, c2) in coordinates.tuple_windows() {
-            cave.put_rocks_between(c1, c2)
+    for rock_path in rock_paths {
+        for (c1, c2) in rock_path.iter().tuple_windows() {
+            cave.put_rocks_between(*c1, *c2)
         }
     }
     cave
 }
 
 
-pub fn solve() {
-    solve_part1();
-    solve_part2();
-}
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<RockPath>;
 
-fn solve_part1() {
-    let sand_entry = Coordinate::new(500, 0);
-    let mut cave = construct_cave(1000, 1000);
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter { name: "part1_cave_size", description: "width and height of the cave in part 1", puzzle: 1000, example: 1000 },
+        Parameter { name: "part2_cave_size", description: "width and height of the cave in part 2", puzzle: 5000, example: 5000 },
+    ];
 
-    while cave.drop_sand_at(sand_entry) {};
-    println!("Part 1: Consumed {} Sand", cave.sand_consumed)
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let rock_paths = input.lines()
+            .map(|line| line.split(" -> ")
+                .map(parse_coordinate)
+                .collect())
+            .collect::<Result<Vec<RockPath>, ParseError>>()?;
+        Ok(rock_paths)
+    }
+
+    fn part1(rock_paths: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        Ok(fill(empty_cave(rock_paths, Part::One, parameters)?).into())
+    }
+
+    fn part2(rock_paths: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        Ok(fill(empty_cave(rock_paths, Part::Two, parameters)?).into())
+    }
+
+    fn pictures(rock_paths: &Self::Input, part: Part, parameters: &Parameters) -> Result<Pictures, Error> {
+        let cave = empty_cave(rock_paths, part, parameters)?;
+        Ok(render::frames(|| cave.clone(), Cave::picture))
+    }
 }
 
-fn solve_part2() {
-    let sand_entry = Coordinate::new(500, 0);
+/// The cave with the rocks, and with the floor in part 2, before any sand falls.
+fn empty_cave(rock_paths: &[RockPath], part: Part, parameters: &Parameters) -> Result<Cave, Error> {
+    let bounds = rock_bounds(rock_paths);
+    match part {
+        Part::One => {
+            // Sand falls out below the deepest rock, and may slide off the right-most one.
+            let size = cave_size(parameters, "part1_cave_size", bounds.max.x.max(bounds.max.y) + 2)?;
+            Ok(construct_cave(rock_paths, size, size))
+        }
+        Part::Two => {
+            // The sand piles up on the floor to a triangle as wide as twice its height.
+            let floor = bounds.max.y + 2;
+            if floor > SAND_SOURCE.x {
+                return Err(Error::no_answer(&format!("The sand would pile up left of x = 0 on the floor at y = {}", floor)));
+            }
+            let width = cave_size(parameters, "part2_cave_size", (SAND_SOURCE.x + floor).max(bounds.max.x) + 2)?;
+            let height = width;
+            let mut cave = construct_cave(rock_paths, width, height);
+
+            let deepest_rock = cave.deepest_rock;
+            cave.put_floor_at(deepest_rock + 2);
+            Ok(cave)
+        }
+    }
+}
 
-    let width = 5000;
-    let height = 5000;
-    let mut cave = construct_cave(width, height);
+/// The area of the rocks and the sand source.
+fn rock_bounds(rock_paths: &[RockPath]) -> BoundingBox<usize> {
+    BoundingBox::of(rock_paths.iter().flatten().copied().chain([SAND_SOURCE])).unwrap()
+}
 
-    let deepest_rock = cave.deepest_rock;
-    let c1 = Coordinate::new(0, deepest_rock + 2);
-    let c2 = Coordinate::new(width, deepest_rock + 2);
-    cave.put_rocks_between(c1, c2);
+fn cave_size(parameters: &Parameters, name: &str, needed: usize) -> Result<usize, Error> {
+    let reason = format!("the rocks and the sand need a cave of at least {}x{}", needed, needed);
+    parameters.at_least(name, needed as i64, &reason)
+}
 
-    while cave.drop_sand_at(sand_entry) {};
-    println!("Part 2: Consumed {} Sand", cave.sand_consumed)
+fn fill(cave: Cave) -> usize {
+    let mut driver = Driver::new(cave);
+    driver.run();
+    driver.simulation().sand_consumed()
 }
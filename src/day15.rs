@@ -1,39 +1,34 @@
-use std::cmp;
 use std::collections::HashSet;
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 
-use itertools::Itertools;
-
-use crate::files;
-
-#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
+use crate::error::Error;
+use crate::geometry::Point;
+use crate::interval::IntervalSet;
+use crate::parameters::{Parameter, Parameters};
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug)]
-struct Deployment {
-    sensor_location: Point,
-    closest_beacon_location: Point,
+pub struct Deployment {
+    sensor_location: Point<i32>,
+    closest_beacon_location: Point<i32>,
 }
 
-impl Deployment {
-    pub fn from_str(str: &String) -> Self {
-        let mut locations = str.splitn(2, ":");
-        let sensor_location = Deployment::extract_point(locations.next().unwrap());
-        let closest_beacon_location = Deployment::extract_point(locations.next().unwrap());
-        Self { sensor_location, closest_beacon_location }
-    }
+impl FromStr for Deployment {
+    type Err = ParseError;
 
-    fn extract_point(string: &str) -> Point {
-        let splits: Vec<_> = string.splitn(4, |c| c == '=' || c == ',').collect();
-        let x = splits[1].parse::<i32>().unwrap();
-        let y = splits[3].parse::<i32>().unwrap();
-        Point { x, y }
+    fn from_str(str: &str) -> Result<Self, ParseError> {
+        let (sensor, beacon) = parse::split_once(str, ":")?;
+        let [x, y] = parse::exact_integers(sensor, "Sensor at x=<number>, y=<number>")?;
+        let sensor_location = Point::new(x, y);
+        let [x, y] = parse::exact_integers(beacon, "closest beacon is at x=<number>, y=<number>")?;
+        let closest_beacon_location = Point::new(x, y);
+        Ok(Self { sensor_location, closest_beacon_location })
     }
+}
 
+impl Deployment {
     pub fn covers(&self, line: i32) -> bool {
         let sensor = self.sensor_location;
         let distance = self.sensor_beacon_distance();
@@ -41,108 +36,89 @@ impl Deployment {
     }
 
     fn sensor_beacon_distance(&self) -> i32 {
-        let p1 = self.sensor_location;
-        let p2 = self.closest_beacon_location;
-        (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
+        self.sensor_location.manhattan(self.closest_beacon_location)
     }
 
-    pub fn covered_points_in(&self, line: &i32, min: &i32, max: &i32) -> RangeInclusive<i32> {
+    /// The x coordinates covered in the line, an empty range if the sensor doesn't reach it.
+    pub fn covered_points_in(&self, line: i32) -> RangeInclusive<i32> {
         let sensor = self.sensor_location;
-        let sensor_beacon_distance = self.sensor_beacon_distance();
-        let delta_vertical = (sensor.y - line).abs();
-        let delta_horizontal = sensor_beacon_distance - delta_vertical;
-        let start = *cmp::max(min, &(sensor.x - delta_horizontal));
-        let end = *cmp::min(max, &(sensor.x + delta_horizontal));
-        start..=end
+        let delta_horizontal = self.sensor_beacon_distance() - (sensor.y - line).abs();
+        sensor.x - delta_horizontal..=sensor.x + delta_horizontal
     }
 }
 
-
-pub fn solve() {
-    solve_part1();
-    solve_part2();
+/// The x coordinates in the line covered by any of the sensors.
+fn covered_in(deployments: &[Deployment], line: i32) -> IntervalSet<i32> {
+    deployments.iter()
+        .filter(|deployment| deployment.covers(line))
+        .map(|deployment| deployment.covered_points_in(line))
+        .collect()
 }
 
 
-fn solve_part1() {
-    let file = "resources/day15.txt";
-    let lines = files::parse_lines_from(file);
-
-    let requested_line = 2_000_000;
-    let min = i32::MIN;
-    let max = i32::MAX;
-    let deployments = lines
-        .iter()
-        .map(|line| Deployment::from_str(line))
-        .collect::<Vec<Deployment>>();
+/// The number of positions in the row where no beacon can be.
+pub fn positions_without_beacon(deployments: &[Deployment], row: i32) -> u64 {
+    let mut covered = covered_in(deployments, row);
+    for deployment in deployments {
+        for location in [deployment.sensor_location, deployment.closest_beacon_location] {
+            if location.y == row {
+                covered.remove(location.x..=location.x);
+            }
+        }
+    }
+    covered.covered()
+}
 
-    let mut covered_xs_in_requested_line = deployments.iter()
-        .filter(|deployment| deployment.covers(requested_line))
-        .flat_map(|deployment| deployment.covered_points_in(&requested_line, &min, &max).collect::<Vec<i32>>())
+/// Slow but obviously correct version of [`positions_without_beacon`], which collects
+/// every covered position.
+pub fn positions_without_beacon_naive(deployments: &[Deployment], row: i32) -> usize {
+    let mut covered = deployments.iter()
+        .filter(|deployment| deployment.covers(row))
+        .flat_map(|deployment| deployment.covered_points_in(row))
         .collect::<HashSet<i32>>();
-
     for deployment in deployments {
-        if deployment.sensor_location.y == requested_line {
-            covered_xs_in_requested_line.remove(&deployment.sensor_location.x);
-        }
-        if deployment.closest_beacon_location.y == requested_line {
-            covered_xs_in_requested_line.remove(&deployment.closest_beacon_location.x);
+        for location in [deployment.sensor_location, deployment.closest_beacon_location] {
+            if location.y == row {
+                covered.remove(&location.x);
+            }
         }
     }
-
-    println!("Part 1: {}", covered_xs_in_requested_line.len())
+    covered.len()
 }
 
 
-fn solve_part2() {
-    let file = "resources/day15.txt";
-    let lines = files::parse_lines_from(file);
+pub struct Day15;
 
-    let min = 0;
-    let max = 4_000_000;
+impl Solution for Day15 {
+    type Input = Vec<Deployment>;
 
-    let deployments = lines
-        .iter()
-        .map(|line| Deployment::from_str(line))
-        .collect::<Vec<Deployment>>();
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter { name: "row", description: "row checked for positions without a beacon in part 1", puzzle: 2_000_000, example: 10 },
+        Parameter { name: "search_max", description: "largest x and y coordinate of the distress beacon in part 2", puzzle: 4_000_000, example: 20 },
+    ];
 
-    let mut yy: i64 = 0;
-    let mut xx: i64 = 0;
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let deployments = input.lines()
+            .map(Deployment::from_str)
+            .collect::<Result<Vec<Deployment>, ParseError>>()?;
+        Ok(deployments)
+    }
 
-    'scan: for y in min..=max {
-        let mut ranges: HashSet<RangeInclusive<i32>> = HashSet::new();
-        for deployment in &deployments {
-            if deployment.covers(y) {
-                let range = deployment.covered_points_in(&y, &min, &max);
-                ranges.insert(range);
-            }
-        }
+    fn part1(deployments: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let requested_line = parameters.get("row")?;
+        Ok(positions_without_beacon(deployments, requested_line).into())
+    }
 
-        // Remove all ranges that are part of another range
-        let ranges_copy = ranges.clone();
-        for r1 in &ranges_copy {
-            for r2 in &ranges_copy {
-                if r1 != r2 && r1.start() >= r2.start() && r1.end() <= r2.end() {
-                    ranges.remove(r1);
-                }
-            }
-        }
+    fn part2(deployments: &Self::Input, parameters: &Parameters) -> Result<Answer, Error> {
+        let max = parameters.at_least("search_max", 0, "the distress beacon is searched from x = 0 and y = 0 on")?;
 
-        // sort
-        let ranges: Vec<_> = ranges.iter()
-            .sorted_by(|a, b| Ord::cmp(&a.start(), &b.start()))
-            .collect();
-
-        // Find the ranges that are not 'connected'
-        for (r1, r2) in ranges.iter().tuple_windows() {
-            if r2.start() - r1.end() > 1 {
-                yy = y as i64;
-                xx = (r1.end() + 1) as i64;
-                break 'scan;
-            }
-        }
-    }
+        let distress_beacon = (0..=max).find_map(|y| {
+            let gaps = covered_in(deployments, y).gaps(0..=max);
+            gaps.first().map(|gap| (*gap.start() as i64, y as i64))
+        });
 
-    let frequency = xx * 4_000_000 + yy;
-    println!("Part 2: {} at ({xx}|{yy})", frequency)
+        let (xx, yy) = distress_beacon.ok_or_else(|| Error::no_answer("No distress beacon found"))?;
+        let frequency = xx * 4_000_000 + yy;
+        Ok(frequency.into())
+    }
 }
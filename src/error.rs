@@ -0,0 +1,68 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::files;
+use crate::parse::ParseError;
+
+/// Everything that can go wrong while solving a puzzle.
+#[derive(Clone, Debug)]
+pub enum Error {
+    Input(files::Error),
+    Parse(ParseError),
+    /// The input was read fine, but the puzzle has no answer for it.
+    NoAnswer(String),
+    /// A puzzle parameter has a value the solver cannot work with.
+    Parameter(String),
+    Panicked(String),
+    /// The solver did not finish within the time it was given.
+    TimedOut(Duration),
+}
+
+impl Error {
+    pub fn no_answer(reason: &str) -> Self {
+        Error::NoAnswer(reason.to_string())
+    }
+
+    /// Resolves the position of a parse error within the input it was raised for.
+    pub fn locate(self, input: &str) -> Self {
+        match self {
+            Error::Parse(error) => Error::Parse(error.locate(input)),
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Input(error) => write!(f, "{}", error),
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::NoAnswer(reason) => write!(f, "No answer: {}", reason),
+            Error::Parameter(message) => write!(f, "Invalid parameter {}", message),
+            Error::Panicked(message) => write!(f, "Panicked: {}", message),
+            Error::TimedOut(timeout) => write!(f, "Timed out after {:.2?}", timeout),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(error) => Some(error),
+            Error::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<files::Error> for Error {
+    fn from(error: files::Error) -> Self {
+        Error::Input(error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
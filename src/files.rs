@@ -1,31 +1,75 @@
-#![allow(dead_code)]
-
-use std::fs::File;
+use std::fmt;
+use std::fs;
 use std::io;
-use std::io::{BufRead, BufReader, Lines, Read};
+use std::io::Read;
 use std::path::Path;
 
-pub fn parse_lines_from<P>(file: P) -> Vec<String> where P: AsRef<Path> {
-    let mut lines: Vec<String> = Vec::new();
-    if let Ok(file_lines) = read_lines(file) {
-        for line in file_lines {
-            if let Ok(ip) = line {
-                lines.push(ip);
-            }
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String),
+    InvalidUtf8(String),
+    Empty,
+    Io(String, io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotFound(origin) => write!(f, "{} does not exist", origin),
+            Error::InvalidUtf8(origin) => write!(f, "{} is not valid UTF-8", origin),
+            Error::Empty => write!(f, "Input is empty"),
+            Error::Io(origin, error) => write!(f, "Could not read {}: {}", origin, error),
         }
     }
-    return lines;
 }
 
-fn read_lines<P>(filename: P) -> io::Result<Lines<BufReader<File>>>
-    where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
+impl Clone for Error {
+    fn clone(&self) -> Self {
+        match self {
+            Error::NotFound(origin) => Error::NotFound(origin.clone()),
+            Error::InvalidUtf8(origin) => Error::InvalidUtf8(origin.clone()),
+            Error::Empty => Error::Empty,
+            Error::Io(origin, error) => Error::Io(origin.clone(), io::Error::new(error.kind(), error.to_string())),
+        }
+    }
 }
 
-pub fn parse_string_from(s: &str) -> io::Result<String> {
-    let mut file = File::open(s)?;
-    let mut s = String::new();
-    file.read_to_string(&mut s)?;
-    Ok(s)
-}
\ No newline at end of file
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(_, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the whole file as normalised text, see [`normalise`].
+pub fn parse_string_from<P>(file: P) -> Result<String, Error> where P: AsRef<Path> {
+    let origin = file.as_ref().display().to_string();
+    let bytes = fs::read(file).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => Error::NotFound(origin.clone()),
+        _ => Error::Io(origin.clone(), error),
+    })?;
+    let string = String::from_utf8(bytes).map_err(|_| Error::InvalidUtf8(origin))?;
+    normalise(&string)
+}
+
+pub fn parse_string_from_stdin() -> Result<String, Error> {
+    let origin = "<stdin>".to_string();
+    let mut bytes = Vec::new();
+    io::stdin().read_to_end(&mut bytes).map_err(|error| Error::Io(origin.clone(), error))?;
+    let string = String::from_utf8(bytes).map_err(|_| Error::InvalidUtf8(origin))?;
+    normalise(&string)
+}
+
+/// Converts CRLF line endings to LF and replaces any trailing blank lines by a single
+/// newline, so that every solver sees `\n`-terminated lines. Leading and in-line
+/// whitespace is significant for some puzzles (e.g. the crate drawing of day 5) and is kept.
+pub fn normalise(string: &str) -> Result<String, Error> {
+    let string = string.replace("\r\n", "\n");
+    let string = string.trim_end_matches(['\n', '\r']);
+    if string.trim().is_empty() {
+        return Err(Error::Empty);
+    }
+    Ok(format!("{}\n", string))
+}
@@ -0,0 +1,343 @@
+//! Random puzzle inputs for stress tests and benchmarks.
+//!
+//! The inputs are syntactically valid and the same for the same day, size and seed, but
+//! not every one has an answer: a short day 10 program never reaches cycle 220, and the
+//! end of a day 12 map may be out of reach.
+
+use std::fmt::Write as _;
+use std::ops::RangeInclusive;
+
+use crate::day13::Value;
+
+/// A small, fast pseudo-random number generator (SplitMix64). Not suited for anything
+/// that has to be unpredictable.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` to `bound - 1`. Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Cannot pick a number below 0");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "Cannot pick a number from an empty range");
+        start + self.below((end - start) as u64 + 1) as i64
+    }
+
+    /// Whether an event with the given probability happens.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// Panics if there is nothing to choose from.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index as u64 + 1) as usize);
+        }
+    }
+}
+
+/// A random input for the day, `None` if there is no generator for it.
+pub fn input(day: u8, size: usize, seed: u64) -> Option<String> {
+    let generator: fn(&mut Random, usize) -> String = match day {
+        1 => day01,
+        2 => day02,
+        3 => day03,
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07,
+        8 => day08,
+        9 => day09,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        _ => return None,
+    };
+    Some(generator(&mut Random::new(seed), size.max(1)))
+}
+
+fn day01(random: &mut Random, elves: usize) -> String {
+    let elves = (0..elves)
+        .map(|_| (0..random.range(1..=10))
+            .map(|_| random.range(1000..=60_000).to_string())
+            .collect::<Vec<String>>()
+            .join("\n"))
+        .collect::<Vec<String>>();
+    elves.join("\n\n") + "\n"
+}
+
+fn day02(random: &mut Random, rounds: usize) -> String {
+    (0..rounds)
+        .map(|_| format!("{} {}\n", random.choose(&['A', 'B', 'C']), random.choose(&['X', 'Y', 'Z'])))
+        .collect()
+}
+
+/// Groups of three rucksacks. The compartments of a rucksack have exactly one item in
+/// common, and the rucksacks of a group exactly one badge.
+fn day03(random: &mut Random, rucksacks: usize) -> String {
+    let mut text = String::new();
+    for _ in 0..rucksacks.div_ceil(3) {
+        let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<char>>();
+        random.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        // Every compartment draws from its own items, so only the shared item and the
+        // badge appear twice.
+        for pools in items.chunks(items.len() / 6).take(6).collect::<Vec<&[char]>>().chunks(2) {
+            let shared = *random.choose(pools[0]);
+            let with_badge = random.below(2) as usize;
+            let length = random.range(4..=16) as usize;
+            for (index, pool) in pools.iter().enumerate() {
+                let mut compartment = vec![shared];
+                if index == with_badge {
+                    compartment.push(badge);
+                }
+                while compartment.len() < length {
+                    compartment.push(*random.choose(pool));
+                }
+                random.shuffle(&mut compartment);
+                text.extend(compartment);
+            }
+            text.push('\n');
+        }
+    }
+    text
+}
+
+fn day04(random: &mut Random, pairs: usize) -> String {
+    let mut sections = || {
+        let (first, last) = (random.range(1..=99), random.range(1..=99));
+        format!("{}-{}", first.min(last), first.max(last))
+    };
+    (0..pairs).map(|_| format!("{},{}\n", sections(), sections())).collect()
+}
+
+/// Nine stacks and moves that never take more crates than a stack holds.
+fn day05(random: &mut Random, moves: usize) -> String {
+    let mut heights = (0..9).map(|_| random.range(1..=8) as usize).collect::<Vec<usize>>();
+    let highest = *heights.iter().max().unwrap();
+    let mut text = String::new();
+    for level in (0..highest).rev() {
+        let line = heights.iter()
+            .map(|height| if level < *height { format!("[{}]", (b'A' + random.below(26) as u8) as char) } else { "   ".to_string() })
+            .collect::<Vec<String>>()
+            .join(" ");
+        writeln!(text, "{}", line).unwrap();
+    }
+    text.push_str(" 1   2   3   4   5   6   7   8   9 \n\n");
+    for _ in 0..moves {
+        let stacks = (0..9).filter(|stack| heights[*stack] > 0).collect::<Vec<usize>>();
+        let from = *random.choose(&stacks);
+        let to = (from + random.range(1..=8) as usize) % 9;
+        let count = random.range(1..=heights[from].min(5) as i64) as usize;
+        heights[from] -= count;
+        heights[to] += count;
+        writeln!(text, "move {} from {} to {}", count, from + 1, to + 1).unwrap();
+    }
+    text
+}
+
+/// Letters from a small alphabet, ending with fourteen different letters so that both
+/// markers exist.
+fn day06(random: &mut Random, characters: usize) -> String {
+    let mut text = (0..characters.saturating_sub(14))
+        .map(|_| (b'a' + random.below(12) as u8) as char)
+        .collect::<String>();
+    let mut marker = ('a'..='z').collect::<Vec<char>>();
+    random.shuffle(&mut marker);
+    text.extend(&marker[..14]);
+    text.push('\n');
+    text
+}
+
+/// A terminal session listing every directory once. New directories are mostly put into
+/// the latest ones, which makes the tree deep.
+fn day07(random: &mut Random, directories: usize) -> String {
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); directories];
+    for directory in 1..directories {
+        let parent = directory - 1 - random.below(directory.min(3) as u64) as usize;
+        children[parent].push(directory);
+    }
+    let mut text = String::from("$ cd /\n");
+    let mut visits = vec![Visit::ENTER(0)];
+    while let Some(visit) = visits.pop() {
+        let directory = match visit {
+            Visit::ENTER(directory) => directory,
+            Visit::LEAVE => {
+                text.push_str("$ cd ..\n");
+                continue;
+            }
+        };
+        if directory != 0 {
+            writeln!(text, "$ cd d{}", directory).unwrap();
+        }
+        text.push_str("$ ls\n");
+        for child in &children[directory] {
+            writeln!(text, "dir d{}", child).unwrap();
+        }
+        for file in 0..random.range(0..=4) {
+            writeln!(text, "{} f{}.txt", random.range(1..=300_000), file).unwrap();
+        }
+        for child in children[directory].iter().rev() {
+            visits.push(Visit::LEAVE);
+            visits.push(Visit::ENTER(*child));
+        }
+    }
+    text
+}
+
+enum Visit {
+    ENTER(usize),
+    LEAVE,
+}
+
+fn day08(random: &mut Random, width: usize) -> String {
+    (0..width)
+        .map(|_| (0..width).map(|_| char::from_digit(random.below(10) as u32, 10).unwrap()).collect::<String>() + "\n")
+        .collect()
+}
+
+fn day09(random: &mut Random, motions: usize) -> String {
+    (0..motions)
+        .map(|_| format!("{} {}\n", random.choose(&['L', 'R', 'U', 'D']), random.range(1..=20)))
+        .collect()
+}
+
+fn day10(random: &mut Random, instructions: usize) -> String {
+    (0..instructions)
+        .map(|_| match random.chance(0.3) {
+            true => "noop\n".to_string(),
+            false => format!("addx {}\n", random.range(-20..=20)),
+        })
+        .collect()
+}
+
+/// Up to eight monkeys with prime divisors, so that the worry levels stay within `u64`.
+fn day11(random: &mut Random, items: usize) -> String {
+    let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19];
+    random.shuffle(&mut primes);
+    let monkeys = random.range(2..=8) as usize;
+    let mut holdings = vec![Vec::new(); monkeys];
+    for _ in 0..items {
+        holdings[random.below(monkeys as u64) as usize].push(random.range(50..=99).to_string());
+    }
+    let mut blocks = Vec::new();
+    for (monkey, holding) in holdings.iter().enumerate() {
+        let operation = match random.below(3) {
+            0 => "old * old".to_string(),
+            1 => format!("old * {}", random.range(2..=19)),
+            _ => format!("old + {}", random.range(1..=8)),
+        };
+        let others = (0..monkeys).filter(|other| *other != monkey).collect::<Vec<usize>>();
+        let (if_true, if_false) = (*random.choose(&others), *random.choose(&others));
+        blocks.push(format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+            monkey, holding.join(", "), operation, primes[monkey], if_true, if_false));
+    }
+    blocks.join("\n")
+}
+
+/// A slope rising from 'S' in the top left to 'E' in the bottom right corner, with
+/// random dents.
+fn day12(random: &mut Random, width: usize) -> String {
+    let width = width.max(2);
+    let mut text = String::new();
+    for y in 0..width {
+        for x in 0..width {
+            let square = match (x, y) {
+                (0, 0) => 'S',
+                _ if x == width - 1 && y == width - 1 => 'E',
+                _ => {
+                    let elevation = ((x + y) * 26 / (2 * width - 1)) as u8;
+                    let dent = if random.chance(0.1) { 1 } else { 0 };
+                    (b'a' + elevation.saturating_sub(dent)) as char
+                }
+            };
+            text.push(square);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Pairs of different packets, as packets in the right or wrong order can't be equal.
+fn day13(random: &mut Random, pairs: usize) -> String {
+    let mut blocks = Vec::new();
+    while blocks.len() < pairs {
+        let (left, right) = (packet(random, 0), packet(random, 0));
+        let (left_value, right_value) = (Value::parse(&left), Value::parse(&right));
+        if let (Ok(left_value), Ok(right_value)) = (left_value, right_value) {
+            if Value::compare(&left_value, &right_value).is_some() {
+                blocks.push(format!("{}\n{}\n", left, right));
+            }
+        }
+    }
+    blocks.join("\n")
+}
+
+fn packet(random: &mut Random, depth: usize) -> String {
+    let values = (0..random.range(0..=4))
+        .map(|_| match depth < 3 && random.chance(0.3) {
+            true => packet(random, depth + 1),
+            false => random.range(0..=10).to_string(),
+        })
+        .collect::<Vec<String>>();
+    format!("[{}]", values.join(","))
+}
+
+/// Paths of horizontal and vertical lines below the sand source, within reach of the
+/// sand in a cave of 1000 by 1000.
+fn day14(random: &mut Random, paths: usize) -> String {
+    let mut text = String::new();
+    for _ in 0..paths {
+        let (mut x, mut y) = (random.range(420..=580), random.range(10..=170));
+        let mut points = vec![format!("{},{}", x, y)];
+        for segment in 0..random.range(1..=4) {
+            let length = random.range(-10..=10);
+            if segment % 2 == 0 {
+                x = (x + length).clamp(400, 600);
+            } else {
+                y = (y + length).clamp(5, 175);
+            }
+            points.push(format!("{},{}", x, y));
+        }
+        writeln!(text, "{}", points.join(" -> ")).unwrap();
+    }
+    text
+}
+
+fn day15(random: &mut Random, sensors: usize) -> String {
+    (0..sensors)
+        .map(|_| {
+            let (x, y) = (random.range(0..=4_000_000), random.range(0..=4_000_000));
+            let distance = random.range(1..=1_000_000);
+            let dx = random.range(-distance..=distance);
+            let dy = (distance - dx.abs()) * if random.chance(0.5) { 1 } else { -1 };
+            format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", x, y, x + dx, y + dy)
+        })
+        .collect()
+}
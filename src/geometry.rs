@@ -0,0 +1,258 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::parse::ParseError;
+
+/// The integer types points can be made of.
+pub trait Number: Copy + Debug + Hash + Ord + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn to_i64(self) -> i64;
+
+    /// The number without loss, even for the largest `u64`.
+    fn to_i128(self) -> i128;
+
+    fn from_i64(value: i64) -> Self;
+
+    /// The next larger number, `None` for the largest one.
+    fn successor(self) -> Option<Self>;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other { self - other } else { other - self }
+    }
+
+    /// -1, 0 or 1, unsigned types are never negative.
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => Self::ZERO - Self::ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! number {
+    ($($number:ty),*) => {
+        $(
+            impl Number for $number {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn to_i64(self) -> i64 {
+                    self as i64
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i64(value: i64) -> Self {
+                    value as $number
+                }
+
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+number!(i32, i64, isize, u32, u64, usize);
+
+/// A point or a vector. Like on the screen, `y` grows downwards.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Number> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    pub fn origin() -> Self {
+        Point { x: T::ZERO, y: T::ZERO }
+    }
+
+    pub fn manhattan(self, other: Point<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of king moves between two points.
+    pub fn chebyshev(self, other: Point<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The vector with both components reduced to -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        Point { x: self.x.signum(), y: self.y.signum() }
+    }
+
+    pub fn step(self, direction: Direction) -> Self where T: Neg<Output=T> {
+        self + direction.vector()
+    }
+}
+
+impl<T: Number> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl<T: Number> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl<T: Number> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Number> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Number> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point { x: self.x * factor, y: self.y * factor }
+    }
+}
+
+impl<T: Number + Neg<Output=T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point { x: -self.x, y: -self.y }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Direction {
+    UP,
+    DOWN,
+    LEFT,
+    RIGHT,
+    UP_LEFT,
+    UP_RIGHT,
+    DOWN_LEFT,
+    DOWN_RIGHT,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::UP, Direction::DOWN, Direction::LEFT, Direction::RIGHT];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::UP, Direction::DOWN, Direction::LEFT, Direction::RIGHT,
+        Direction::UP_LEFT, Direction::UP_RIGHT, Direction::DOWN_LEFT, Direction::DOWN_RIGHT,
+    ];
+
+    /// Parses one of the letters `L`, `R`, `U` and `D`.
+    pub fn parse(letter: &str) -> Result<Self, ParseError> {
+        match letter {
+            "L" => Ok(Direction::LEFT),
+            "R" => Ok(Direction::RIGHT),
+            "U" => Ok(Direction::UP),
+            "D" => Ok(Direction::DOWN),
+            _ => Err(ParseError::new(letter, "Expected a direction 'L', 'R', 'U' or 'D'")),
+        }
+    }
+
+    /// The vector of one step into this direction.
+    pub fn vector<T: Number + Neg<Output=T>>(self) -> Point<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Direction::UP => Point::new(zero, -one),
+            Direction::DOWN => Point::new(zero, one),
+            Direction::LEFT => Point::new(-one, zero),
+            Direction::RIGHT => Point::new(one, zero),
+            Direction::UP_LEFT => Point::new(-one, -one),
+            Direction::UP_RIGHT => Point::new(one, -one),
+            Direction::DOWN_LEFT => Point::new(-one, one),
+            Direction::DOWN_RIGHT => Point::new(one, one),
+        }
+    }
+}
+
+/// The smallest rectangle containing a set of points, both corners included.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Number> BoundingBox<T> {
+    /// Returns `None` if there are no points.
+    pub fn of(points: impl IntoIterator<Item=Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = BoundingBox { min: first, max: first };
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the box until it contains the point.
+    pub fn include(&mut self, point: Point<T>) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+/// The points of a straight line from `from` to `to`, both ends included, as drawn by
+/// Bresenham's algorithm. Horizontal, vertical and diagonal lines contain every point between.
+pub fn line<T: Number>(from: Point<T>, to: Point<T>) -> impl Iterator<Item=Point<T>> {
+    let (x0, y0, x1, y1) = (from.x.to_i64(), from.y.to_i64(), to.x.to_i64(), to.y.to_i64());
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+    let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let point = Point::new(T::from_i64(x), T::from_i64(y));
+        if x == x1 && y == y1 {
+            done = true;
+        } else {
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+        Some(point)
+    })
+}
@@ -0,0 +1,161 @@
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Point};
+use crate::parse::ParseError;
+
+/// A rectangular map of cells stored row by row. A cell is at the [`Point`] of its column `x`
+/// and row `y`, both counted from the top left corner.
+///
+/// Cells can be read checked with [`Grid::get`], which returns `None` outside of the grid,
+/// or unchecked by indexing with a point, which panics outside of the grid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells given row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "A {}x{} grid needs {} cells", width, height, width * height);
+        Grid { width, height, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, cell: T) -> Self where T: Clone {
+        Grid { width, height, cells: vec![cell; width * height] }
+    }
+
+    /// Parses a map with one character per cell, every line being one row. `cell` converts
+    /// a character or explains what was expected instead.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, ParseError> where F: FnMut(char) -> Result<T, String> {
+        let mut lines = input.lines().peekable();
+        let width = lines.peek().map(|line| line.chars().count()).unwrap_or(0);
+        if width == 0 {
+            return Err(ParseError::new(input, "Expected a map with at least one cell"));
+        }
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for line in lines {
+            if line.chars().count() != width {
+                return Err(ParseError::new(line, format!("Expected a row of {} cells", width)));
+            }
+            for (index, c) in line.char_indices() {
+                let value = cell(c).map_err(|message| ParseError::new(&line[index..index + c.len_utf8()], message))?;
+                cells.push(value);
+            }
+            height += 1;
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Point<usize>) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Point<usize>) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Point<usize>) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    fn index_of(&self, position: Point<usize>) -> Option<usize> {
+        self.contains(position).then(|| position.x + position.y * self.width)
+    }
+
+    /// The position one step into the direction, or `None` if that step leaves the grid.
+    pub fn step(&self, position: Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        let vector = direction.vector::<isize>();
+        let position = Point::new(position.x.checked_add_signed(vector.x)?, position.y.checked_add_signed(vector.y)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// The positions of the up to four cells sharing a side with the cell.
+    pub fn neighbours(&self, position: Point<usize>) -> impl Iterator<Item=Point<usize>> + '_ {
+        Direction::ORTHOGONAL.into_iter().filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// The positions of the up to eight cells around the cell, including the diagonal ones.
+    pub fn neighbours_all_around(&self, position: Point<usize>) -> impl Iterator<Item=Point<usize>> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// The positions reached by stepping into the direction from the cell until leaving the
+    /// grid. The cell itself is not part of the ray.
+    pub fn ray(&self, position: Point<usize>, direction: Direction) -> impl Iterator<Item=Point<usize>> + '_ {
+        let mut current = position;
+        std::iter::from_fn(move || {
+            current = self.step(current, direction)?;
+            Some(current)
+        })
+    }
+
+    /// The cells of row `y` from left to right, panics if there is no such row.
+    pub fn row(&self, y: usize) -> impl Iterator<Item=&T> {
+        assert!(y < self.height, "Row {} is outside of the {}x{} grid", y, self.width, self.height);
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// The cells of column `x` from top to bottom, panics if there is no such column.
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        assert!(x < self.width, "Column {} is outside of the {}x{} grid", x, self.width, self.height);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// All positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item=Point<usize>> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |index| Point::new(index % width, index / width))
+    }
+
+    /// All cells with their positions row by row.
+    pub fn iter(&self) -> impl Iterator<Item=(Point<usize>, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// A grid of the same size with every cell converted.
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(cell).collect() }
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point<usize>> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    /// Draws the grid with one character per cell and a line break after every row.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            text.extend(self.row(y).map(&mut cell));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point<usize>) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", position.x, position.y, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Point<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", position.x, position.y, width, height))
+    }
+}
@@ -0,0 +1,143 @@
+use std::ops::RangeInclusive;
+
+use crate::geometry::Number;
+
+/// A set of integers stored as sorted, disjoint ranges. Overlapping and adjacent ranges
+/// are merged on insertion, so `1..=3` and `4..=6` are kept as `1..=6`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Number> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Adds all numbers of the range. Empty ranges like `5..=4` are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // The ranges that overlap or touch the new one are replaced by their union.
+        let first = self.ranges.partition_point(|(_, e)| e.successor().is_some_and(|next| next < start));
+        let mut last = first;
+        while last < self.ranges.len() && end.successor().is_none_or(|next| self.ranges[last].0 <= next) {
+            start = start.min(self.ranges[last].0);
+            end = end.max(self.ranges[last].1);
+            last += 1;
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    /// Removes all numbers of the range.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let mut remaining = Vec::with_capacity(self.ranges.len() + 1);
+        for &(s, e) in &self.ranges {
+            if e < start || s > end {
+                remaining.push((s, e));
+                continue;
+            }
+            if s < start {
+                remaining.push((s, start - T::ONE));
+            }
+            if e > end {
+                remaining.push((end + T::ONE, e));
+            }
+        }
+        self.ranges = remaining;
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let (start, end) = (a.0.max(b.0), a.1.min(b.1));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a.1 < b.1 { i += 1 } else { j += 1 }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many numbers the set contains, at most `u64::MAX`. Only a set of all 2^64
+    /// numbers of `i64` or `u64` contains more.
+    pub fn covered(&self) -> u64 {
+        let covered: u128 = self.ranges.iter()
+            .map(|(start, end)| (end.to_i128() - start.to_i128()) as u128 + 1)
+            .sum();
+        u64::try_from(covered).unwrap_or(u64::MAX)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// Whether every number of the range is in the set.
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (start, end) = range.into_inner();
+        start > end || self.find(start).is_some_and(|(_, e)| end <= e)
+    }
+
+    /// Whether every number of the other set is in this set.
+    pub fn is_superset(&self, other: &IntervalSet<T>) -> bool {
+        other.iter().all(|range| self.contains_range(range))
+    }
+
+    pub fn overlaps(&self, other: &IntervalSet<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    fn find(&self, value: T) -> Option<(T, T)> {
+        let index = self.ranges.partition_point(|(_, end)| *end < value);
+        self.ranges.get(index).copied().filter(|(start, _)| *start <= value)
+    }
+
+    /// The ranges within `bounds` that are not in the set.
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        let mut outside = IntervalSet::new();
+        outside.insert(bounds);
+        outside.difference(self).iter().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+}
+
+impl<T: Number> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item=RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
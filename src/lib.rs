@@ -0,0 +1,40 @@
+//! Solutions of Advent of Code 2022. Every day is a [`Solution`](solution::Solution)
+//! registered in [`runner::DAYS`]; the data structures of the days can be used on their own.
+
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod files;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod parameters;
+pub mod parse;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+pub mod trace;
+pub mod watch;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
@@ -1,8 +1,519 @@
-extern crate core;
+use std::env;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-mod files;
-mod day15;
+use advent_of_code_2022::bench;
+use advent_of_code_2022::client::{Client, Verdict};
+use advent_of_code_2022::config::Config;
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::files;
+use advent_of_code_2022::generate;
+use advent_of_code_2022::grid::Grid;
+use advent_of_code_2022::parameters::Override;
+use advent_of_code_2022::render::{self, Colour, Frames};
+use advent_of_code_2022::report;
+use advent_of_code_2022::runner::{self, Day, InputSource};
+use advent_of_code_2022::scaffold;
+use advent_of_code_2022::solution::Part;
+use advent_of_code_2022::trace;
+use advent_of_code_2022::watch::{self, Watcher};
 
-fn main() {
-    day15::solve()
+use crate::cli::{BenchOptions, Command, FetchOptions, Format, GenerateOptions, Rendering, RunOptions, Selection, SubmitOptions, WatchOptions};
+
+mod cli;
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let code = match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Run(options) => run(options),
+        Command::Bench(options) => bench(options),
+        Command::Params(day) => params(day),
+        Command::Generate(options) => generate(options),
+        Command::Fetch(options) => fetch(options),
+        Command::Submit(options) => submit(options),
+        Command::New(day) => new(day),
+        Command::Watch(options) => watch(options),
+    };
+    if let Err(message) = trace::finish() {
+        eprintln!("{}", message);
+    }
+    code
+}
+
+fn run(mut options: RunOptions) -> ExitCode {
+    let Plan { days, parts, overrides, timeout } = match prepare(&options) {
+        Ok(plan) => plan,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    // The pictures are drawn from the input once more, which stdin doesn't give twice.
+    if options.render.is_some() && options.source == InputSource::Stdin {
+        match files::parse_string_from_stdin() {
+            Ok(input) => options.source = InputSource::Inline(input),
+            Err(error) => {
+                eprintln!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if options.all_profiles {
+        return compare_profiles(&days, &parts, &overrides, timeout);
+    }
+
+    let outcomes = match options.format {
+        Format::Text => {
+            let outcomes = runner::run(&days, &parts, &options.source, &overrides, timeout, runner::print_outcome);
+            runner::print_summary(&outcomes);
+            outcomes
+        }
+        Format::Json | Format::Csv => {
+            let outcomes = runner::run(&days, &parts, &options.source, &overrides, timeout, |_| ());
+            match options.format {
+                Format::Json => println!("{}", report::json(&outcomes)),
+                _ => println!("{}", report::csv(&outcomes)),
+            }
+            outcomes
+        }
+    };
+
+    let drawn = match options.render {
+        Some(rendering) => draw(&days, &outcomes, &options.source, &overrides, rendering, options.frames.as_deref()),
+        None => true,
+    };
+    if drawn && outcomes.iter().all(|outcome| outcome.succeeded()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Draws the solved parts of the days that have pictures. Returns whether all of them could
+/// be drawn.
+fn draw(days: &[&Day], outcomes: &[runner::Outcome], source: &InputSource, overrides: &[Override],
+        rendering: Rendering, frames: Option<&Path>) -> bool {
+    let separator = if rendering == Rendering::Ansi { "\n" } else { " " };
+    let mut drawn = true;
+    for day in days {
+        let solved: Vec<Part> = outcomes.iter()
+            .filter(|outcome| outcome.day == day.number && outcome.succeeded())
+            .map(|outcome| outcome.part)
+            .collect();
+        if solved.is_empty() {
+            continue;
+        }
+        let input = match source.read(day) {
+            Ok(input) => input,
+            Err(error) => {
+                println!("Day {:02}: {}", day.number, error);
+                drawn = false;
+                continue;
+            }
+        };
+        let parameters = day.resolve_parameters(source.is_example(), overrides);
+        for part in solved {
+            let name = format!("day{:02}-part{}", day.number, part.number());
+            let result = day.pictures(&input, part, &parameters)
+                .map_err(|error| error.to_string())
+                .and_then(|pictures| draw_part(&pictures, &name, rendering, frames));
+            match result {
+                Ok(None) => {}
+                Ok(Some(drawing)) => println!("Day {:02} part {}:{}{}", day.number, part.number(), separator, drawing),
+                Err(message) => {
+                    println!("Day {:02} part {}: {}", day.number, part.number(), message);
+                    drawn = false;
+                }
+            }
+        }
+    }
+    drawn
+}
+
+/// The drawing of the last picture, or of all of them as frames. `None` if there are no
+/// pictures.
+fn draw_part(pictures: &[Grid<Colour>], name: &str, rendering: Rendering, frames: Option<&Path>) -> Result<Option<String>, String> {
+    let Some(last) = pictures.last() else { return Ok(None) };
+    let scale = render::fitting_scale(last, 800);
+    match (rendering, frames) {
+        (Rendering::Ansi, _) => Ok(Some(render::ansi(last, |colour| ('█', *colour)).trim_end().to_string())),
+        (Rendering::Image(format), None) => {
+            let file = PathBuf::from(format!("{}.{}", name, format.extension()));
+            std::fs::write(&file, format.encode(last, scale))
+                .map_err(|error| format!("Could not write {}: {}", file.display(), error))?;
+            Ok(Some(format!("drew {}", file.display())))
+        }
+        (Rendering::Image(format), Some(directory)) => {
+            let directory = directory.join(name);
+            let written = Frames::create(&directory, format, scale).and_then(|mut frames| {
+                for picture in pictures {
+                    frames.write(picture)?;
+                }
+                Ok(frames.written())
+            });
+            written.map(|written| Some(format!("drew {} frames into {}", written, directory.display())))
+                .map_err(|error| format!("Could not write the frames into {}: {}", directory.display(), error))
+        }
+    }
+}
+
+/// Solves the default puzzle inputs and those of every profile and prints a table of the
+/// answers. Missing inputs are left out, other errors fail the run.
+fn compare_profiles(days: &[&Day], parts: &[Part], overrides: &[Override], timeout: Option<Duration>) -> ExitCode {
+    let profiles = match runner::profiles() {
+        Ok(profiles) if profiles.is_empty() => {
+            eprintln!("There are no profiles, put their inputs in {}/<profile>/dayNN.txt", runner::RESOURCES);
+            return ExitCode::from(2);
+        }
+        Ok(profiles) => profiles,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut inputs = vec!["default".to_string()];
+    let mut sources = vec![InputSource::Puzzle];
+    for profile in profiles {
+        inputs.push(profile.clone());
+        sources.push(InputSource::Profile(profile));
+    }
+    let columns: Vec<Vec<runner::Outcome>> = sources.iter()
+        .map(|source| runner::run(days, parts, source, overrides, timeout, |_| ()))
+        .collect();
+    print!("{}", report::table(&inputs, &columns));
+
+    let failed = columns.iter().flatten().any(|outcome| match &outcome.result {
+        Ok(_) | Err(Error::Input(files::Error::NotFound(_))) => false,
+        Err(_) => true,
+    });
+    if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+fn bench(options: BenchOptions) -> ExitCode {
+    let prepared = prepare(&options.run)
+        .and_then(|plan| match &options.compare {
+            Some(file) => Ok((plan, bench::load(file)?)),
+            None => Ok((plan, Vec::new())),
+        });
+    let (Plan { days, parts, overrides, timeout }, previous) = match prepared {
+        Ok(prepared) => prepared,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut succeeded = true;
+    let mut measurements = Vec::new();
+    bench::print_header(options.compare.is_some());
+    for day in days {
+        let input: Arc<str> = match options.run.source.read(day) {
+            Ok(input) => input.into(),
+            Err(error) => {
+                println!("Day {:02}: {}", day.number, error);
+                succeeded = false;
+                continue;
+            }
+        };
+        let parameters = day.resolve_parameters(options.run.source.is_example(), &overrides);
+        match runner::measure_guarded(day, &input, &parts, &parameters, options.runs, timeout) {
+            Ok(timings) => {
+                for measurement in bench::measurements(day.number, &timings) {
+                    let change = bench::change(&measurement, &previous);
+                    bench::print_measurement(&measurement, change, options.threshold);
+                    succeeded &= change.is_none_or(|change| change <= options.threshold);
+                    measurements.push(measurement);
+                }
+            }
+            Err(error) => {
+                println!("Day {:02}: {}", day.number, error);
+                succeeded = false;
+            }
+        }
+    }
+
+    if let Some(file) = &options.save {
+        if let Err(message) = bench::save(file, &measurements) {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    }
+    if succeeded { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+/// The days and parts to solve, and the parameter overrides and time limit to solve them with.
+struct Plan {
+    days: Vec<&'static Day>,
+    parts: Vec<Part>,
+    overrides: Vec<Override>,
+    timeout: Option<Duration>,
+}
+
+/// Works out what to solve, and starts tracing if asked to.
+fn prepare(options: &RunOptions) -> Result<Plan, String> {
+    let days: Vec<&Day> = match &options.selection {
+        Selection::All => runner::DAYS.iter().collect(),
+        Selection::Days(range) => solved_days(range.clone())?,
+    };
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    let overrides = overrides(&days, options)?;
+    match (options.trace, &options.trace_file) {
+        (Some(level), Some(file)) => trace::to_file(level, file)?,
+        (Some(level), None) => trace::to_stderr(level),
+        (None, _) => {}
+    }
+    Ok(Plan { days, parts, overrides, timeout: options.timeout })
+}
+
+/// The solved days of the range. The others are skipped with a note, unless none is solved.
+fn solved_days(range: RangeInclusive<u8>) -> Result<Vec<&'static Day>, String> {
+    let (solved, unsolved): (Vec<u8>, Vec<u8>) = range.partition(|number| runner::find_day(*number).is_some());
+    match (solved.is_empty(), unsolved.as_slice()) {
+        (true, [number]) => return Err(format!("Day {} is not solved yet", number)),
+        (true, _) => return Err(format!("None of days {} to {} is solved yet", unsolved[0], unsolved[unsolved.len() - 1])),
+        (false, []) => {}
+        (false, _) => {
+            let numbers: Vec<String> = unsolved.iter().map(u8::to_string).collect();
+            eprintln!("Skipping the days that are not solved yet: {}", numbers.join(", "));
+        }
+    }
+    Ok(solved.into_iter().filter_map(runner::find_day).collect())
+}
+
+/// Collects the parameters of the config file followed by those of the command line, so
+/// that the command line wins. Every parameter has to exist for the day it is meant for.
+fn overrides(days: &[&Day], options: &RunOptions) -> Result<Vec<Override>, String> {
+    let mut overrides = Config::load(options.config.as_deref())?.overrides()?;
+    overrides.extend(options.overrides.iter().cloned());
+    for o in &overrides {
+        let known = match o.day {
+            Some(number) => runner::find_day(number).is_some_and(|day| day.has_parameter(&o.name)),
+            None => days.iter().any(|day| day.has_parameter(&o.name)),
+        };
+        if !known {
+            return Err(match o.day {
+                Some(number) => format!("Day {} has no parameter '{}'", number, o.name),
+                None => format!("None of the selected days has a parameter '{}'", o.name),
+            });
+        }
+    }
+    Ok(overrides)
+}
+
+fn params(day: Option<u8>) -> ExitCode {
+    let days: Vec<&Day> = match day {
+        None => runner::DAYS.iter().collect(),
+        Some(number) => match runner::find_day(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {} is not solved yet", number);
+                return ExitCode::from(2);
+            }
+        },
+    };
+    for day in days.iter().filter(|day| !day.parameters.is_empty()) {
+        println!("{:<18} {:>10} {:>10}", format!("Day {:02}:", day.number), "puzzle", "example");
+        for parameter in day.parameters {
+            println!("  {:<16} {:>10} {:>10}  {}", parameter.name, parameter.puzzle, parameter.example, parameter.description);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn generate(options: GenerateOptions) -> ExitCode {
+    let Some(input) = generate::input(options.day, options.size, options.seed) else {
+        eprintln!("There is no generator for day {}", options.day);
+        return ExitCode::from(2);
+    };
+    match &options.output {
+        None => print!("{}", input),
+        Some(file) => if let Err(error) = std::fs::write(file, input) {
+            eprintln!("Could not write {}: {}", file.display(), error);
+            return ExitCode::FAILURE;
+        },
+    }
+    ExitCode::SUCCESS
+}
+
+fn fetch(options: FetchOptions) -> ExitCode {
+    let fetched = Config::load(options.config.as_deref())
+        .and_then(|config| Client::from_config(&config, Path::new(runner::RESOURCES)))
+        .and_then(|client| client.fetch(options.day, options.force));
+    match fetched {
+        Ok(file) => {
+            println!("Input of day {} is in {}", options.day, file.display());
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(options: SubmitOptions) -> ExitCode {
+    let submitted = Config::load(options.config.as_deref()).and_then(|config| {
+        let client = Client::from_config(&config, Path::new(runner::RESOURCES))?;
+        let answer = match &options.answer {
+            Some(answer) => answer.clone(),
+            None => solve(options.day, options.part, &config)?,
+        };
+        println!("Day {:02} part {}: submitting {}", options.day, options.part.number(), answer);
+        client.submit(options.day, options.part, &answer)
+    });
+    match submitted {
+        Ok(submission) => {
+            let known = if submission.sent { "" } else { " (known from an earlier submission)" };
+            println!("{}{}", submission.verdict, known);
+            if submission.verdict == Verdict::CORRECT { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The answer to the puzzle input of the day, with the parameters of the config file.
+fn solve(number: u8, part: Part, config: &Config) -> Result<String, String> {
+    let day = runner::find_day(number).ok_or_else(|| format!("Day {} is not solved yet", number))?;
+    let input = InputSource::Puzzle.read(day).map_err(|error| error.to_string())?;
+    let overrides = config.overrides()?;
+    let parameters = day.resolve_parameters(false, &overrides);
+    runner::solve_guarded(day, &input.into(), part, &parameters, Some(runner::DEFAULT_TIMEOUT))
+        .map(|answer| answer.to_string())
+        .map_err(|error| format!("Day {:02} part {}: {}", number, part.number(), error))
+}
+
+fn new(day: u8) -> ExitCode {
+    match scaffold::create(Path::new("."), day) {
+        Ok(files) => {
+            for file in files {
+                println!("Wrote {}", file.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves the day whenever one of its input files changes. The code of the day can't be
+/// reloaded, a change of its source rebuilds the program and restarts it with the answers
+/// of the new code.
+fn watch(options: WatchOptions) -> ExitCode {
+    let Plan { days, parts, overrides, timeout } = match prepare(&options.run) {
+        Ok(plan) => plan,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(2);
+        }
+    };
+    // Once rebuilt, the program can no longer be found by asking for the running one.
+    let program = match env::current_exe() {
+        Ok(program) => program,
+        Err(error) => {
+            eprintln!("Could not find the program to restart after a rebuild: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let day = days[0];
+    let sources = match &options.run.source {
+        InputSource::Puzzle => vec![InputSource::Example, InputSource::Puzzle],
+        source => vec![source.clone()],
+    };
+    let mut inputs = Watcher::new(sources.iter().filter_map(|source| source.file(day)).collect());
+    let mut source = Watcher::new(vec![PathBuf::from(format!("src/day{:02}.rs", day.number))]);
+
+    let mut previous = Vec::new();
+    let mut run = 0;
+    loop {
+        run += 1;
+        println!("Run {}:", run);
+        let outcomes: Vec<_> = sources.iter()
+            .flat_map(|source| runner::run(&[day], &parts, source, &overrides, timeout, |_| ()))
+            .collect();
+        for outcome in &outcomes {
+            println!("{}", watch::describe(outcome, &previous));
+        }
+        previous = outcomes;
+
+        let files: Vec<String> = inputs.files().map(|file| file.display().to_string()).collect();
+        println!("\nWatching {}", files.join(", "));
+        let changed = loop {
+            thread::sleep(options.interval);
+            for file in source.changed() {
+                println!("{} changed, rebuilding", file.display());
+                match rebuild() {
+                    Ok(()) => return restart(&program),
+                    Err(message) => println!("{}, watching on with the old code", message),
+                }
+            }
+            let changed = inputs.changed();
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        let changed: Vec<String> = changed.iter().map(|file| file.display().to_string()).collect();
+        println!("{} changed\n", changed.join(", "));
+    }
+}
+
+/// Builds the program again with the cargo and the profile it was built with, `dev` or
+/// `release`.
+fn rebuild() -> Result<(), String> {
+    let mut command = process::Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    command.args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    match command.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err("The build failed".to_string()),
+        Err(error) => Err(format!("Could not run cargo: {}", error)),
+    }
+}
+
+/// Runs the rebuilt program with the same arguments in place of this one, or until it
+/// exits where a process can't be replaced.
+fn restart(program: &Path) -> ExitCode {
+    if let Err(message) = trace::finish() {
+        eprintln!("{}", message);
+    }
+    let mut command = process::Command::new(program);
+    command.args(env::args_os().skip(1));
+    #[cfg(unix)]
+    let result = {
+        use std::os::unix::process::CommandExt;
+        Err::<ExitCode, _>(command.exec())
+    };
+    #[cfg(not(unix))]
+    let result = command.status().map(|status| ExitCode::from(status.code().unwrap_or(1) as u8));
+    result.unwrap_or_else(|error| {
+        eprintln!("Could not restart {}: {}", program.display(), error);
+        ExitCode::FAILURE
+    })
 }
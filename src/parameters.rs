@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use crate::error::Error;
+
+/// A constant of a puzzle that is given in the puzzle text rather than in the input,
+/// like the row day 15 asks about. The examples often use smaller values than the
+/// real puzzle, so every parameter has a default for both.
+#[derive(Debug)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub puzzle: i64,
+    pub example: i64,
+}
+
+/// A value for a parameter given on the command line (`[dayNN.]name=value`) or in the
+/// config file. Without a day it applies to every selected day that has the parameter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Override {
+    pub day: Option<u8>,
+    pub name: String,
+    pub value: i64,
+}
+
+impl Override {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (key, value) = text.split_once('=')
+            .ok_or_else(|| format!("Expected <name>=<value>, got '{}'", text))?;
+        let value = value.trim().parse::<i64>()
+            .map_err(|_| format!("Value of '{}' is not a number", key.trim()))?;
+        let (day, name) = match key.trim().split_once('.') {
+            Some((day, name)) => (Some(parse_day(day)?), name),
+            None => (None, key.trim()),
+        };
+        Ok(Override { day, name: name.to_string(), value })
+    }
+}
+
+/// Parses a day as written on the command line, in parameter names and in config sections,
+/// `day15` or `15`. Only the days of Advent of Code, 1 to 25, are valid.
+pub fn parse_day(text: &str) -> Result<u8, String> {
+    let text = text.trim();
+    let day = text.strip_prefix("day").unwrap_or(text).parse::<u8>()
+        .map_err(|_| format!("'{}' is not a valid day", text))?;
+    match day {
+        1..=25 => Ok(day),
+        _ => Err(format!("There is no day {} in Advent of Code", day)),
+    }
+}
+
+/// The parameter values a day is solved with.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Parameters {
+    values: Vec<(&'static str, i64)>,
+}
+
+impl Parameters {
+    /// Takes the puzzle or example defaults of the declared parameters and applies the
+    /// overrides meant for this day, later overrides winning over earlier ones.
+    pub fn resolve(day: u8, declared: &'static [Parameter], example: bool, overrides: &[Override]) -> Self {
+        let values = declared.iter()
+            .map(|parameter| {
+                let default = if example { parameter.example } else { parameter.puzzle };
+                let value = overrides.iter()
+                    .filter(|o| o.day.is_none_or(|d| d == day) && o.name == parameter.name)
+                    .map(|o| o.value)
+                    .next_back()
+                    .unwrap_or(default);
+                (parameter.name, value)
+            })
+            .collect();
+        Parameters { values }
+    }
+
+    /// Returns the value of a declared parameter converted to the type the solver needs.
+    pub fn get<T>(&self, name: &str) -> Result<T, Error> where T: TryFrom<i64>, T::Error: Display {
+        let value = self.values.iter()
+            .find(|(declared, _)| *declared == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("Parameter '{}' is not declared", name));
+        T::try_from(value).map_err(|error| Error::Parameter(format!("{} = {}: {}", name, value, error)))
+    }
+
+    /// Like [`Parameters::get`] for a parameter that can't be less than `min`, `reason`
+    /// telling why.
+    pub fn at_least<T>(&self, name: &str, min: i64, reason: &str) -> Result<T, Error> where T: TryFrom<i64>, T::Error: Display {
+        let value = self.get::<i64>(name)?;
+        if value < min {
+            return Err(Error::Parameter(format!("{} = {}: {}", name, value, reason)));
+        }
+        self.get(name)
+    }
+}
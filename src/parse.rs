@@ -0,0 +1,205 @@
+use std::fmt;
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+
+/// A syntax error in a puzzle input.
+///
+/// Parsers create the error from the offending snippet, which has to be a slice of the
+/// input handed to [`Solution::parse`](crate::solution::Solution::parse). The runner then
+/// calls [`ParseError::locate`] with that input to find the line and column of the snippet.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    message: String,
+    snippet: String,
+    address: usize,
+    location: Option<Location>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The complete line containing the snippet.
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(snippet: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            message: message.into(),
+            snippet: snippet.to_string(),
+            address: snippet.as_ptr() as usize,
+            location: None,
+        }
+    }
+
+    /// Resolves line and column of the snippet within `input`. The error is returned
+    /// unchanged if the snippet is not a slice of `input`.
+    pub fn locate(mut self, input: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        if self.location.is_some() || self.address < start || self.address > start + input.len() {
+            return self;
+        }
+        let offset = self.address - start;
+        let line_start = input[..offset].rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let line_end = input[offset..].find('\n').map(|index| offset + index).unwrap_or(input.len());
+        self.location = Some(Location {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end].to_string(),
+        });
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            None => write!(f, "{}: '{}'", self.message, self.snippet),
+            Some(location) => {
+                let gutter = " ".repeat(location.line.to_string().len());
+                let available = location.text.chars().count() + 1 - location.column;
+                let underline = self.snippet.chars().count().clamp(1, available.max(1));
+                writeln!(f, "{} at line {}, column {}", self.message, location.line, location.column)?;
+                writeln!(f, "{} |", gutter)?;
+                writeln!(f, "{} | {}", location.line, location.text)?;
+                write!(f, "{} | {}{}", gutter, " ".repeat(location.column - 1), "^".repeat(underline))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the whole snippet as a number.
+pub fn number<T: FromStr>(snippet: &str) -> Result<T, ParseError> {
+    snippet.parse::<T>().map_err(|_| ParseError::new(snippet, "Expected a number"))
+}
+
+/// Splits the snippet at the first occurrence of the delimiter.
+pub fn split_once<'a>(snippet: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    snippet.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(snippet, format!("Expected '{}'", delimiter)))
+}
+
+/// The blocks of lines separated by blank lines. Leading and trailing blank lines are
+/// ignored, so the last block doesn't need a blank line after it. Every block is a slice
+/// of the input without its final line break.
+pub fn blocks(input: &str) -> impl Iterator<Item=&str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        let start = rest.trim_start_matches(['\r', '\n']);
+        if start.is_empty() {
+            return None;
+        }
+        let mut end = 0;
+        for line in start.split_inclusive('\n') {
+            if line.trim_end_matches(['\r', '\n']).is_empty() {
+                break;
+            }
+            end += line.len();
+        }
+        rest = &start[end..];
+        Some(start[..end].trim_end_matches(['\r', '\n']))
+    })
+}
+
+/// Parses all integers in the snippet, whatever is between them. A `-` directly in front
+/// of a number is its sign, unless it follows a digit like in `2-4`.
+pub fn integers<T: FromStr>(snippet: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = snippet.as_bytes();
+    let mut integers = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let signed = bytes[index] == b'-' && (index == 0 || !bytes[index - 1].is_ascii_digit())
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_digit);
+        if !signed && !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        index += 1;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        integers.push(number(&snippet[start..index])?);
+    }
+    Ok(integers)
+}
+
+/// Parses exactly `N` integers from the snippet like [`integers`]. `expected` describes
+/// the snippet for the error message, for example `x=<number>, y=<number>`.
+pub fn exact_integers<T: FromStr, const N: usize>(snippet: &str, expected: &str) -> Result<[T; N], ParseError> {
+    integers(snippet)?
+        .try_into()
+        .map_err(|_| ParseError::new(snippet, format!("Expected '{}'", expected)))
+}
+
+/// A regular expression a whole snippet has to match, with named groups for its fields.
+///
+/// Patterns are usually compiled once with `lazy_static!`:
+///
+/// ```
+/// use advent_of_code_2022::parse::Pattern;
+///
+/// let pattern = Pattern::new(r"move (?P<count>\d+) from (?P<from>\d+)", "move <count> from <from>");
+/// let record = pattern.matches("move 3 from 1").unwrap();
+/// assert_eq!(record.number::<u32>("count"), Ok(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    regex: Regex,
+    expected: &'static str,
+}
+
+impl Pattern {
+    /// Panics if the regular expression is invalid. `expected` describes the snippet for
+    /// the error message if it doesn't match.
+    pub fn new(regex: &str, expected: &'static str) -> Self {
+        let regex = Regex::new(&format!("^(?:{})$", regex))
+            .unwrap_or_else(|error| panic!("Invalid pattern for '{}': {}", expected, error));
+        Pattern { regex, expected }
+    }
+
+    pub fn matches<'a>(&self, snippet: &'a str) -> Result<Record<'_, 'a>, ParseError> {
+        self.regex.captures(snippet)
+            .map(|captures| Record { pattern: self, captures, snippet })
+            .ok_or_else(|| ParseError::new(snippet, format!("Expected '{}'", self.expected)))
+    }
+}
+
+/// The fields of a snippet matched by a [`Pattern`]. Errors in fields point at the field.
+#[derive(Debug)]
+pub struct Record<'p, 'a> {
+    pattern: &'p Pattern,
+    captures: Captures<'a>,
+    snippet: &'a str,
+}
+
+impl<'a> Record<'_, 'a> {
+    /// The text of a field. Fields of optional groups that didn't match are empty.
+    /// Panics if the pattern has no group with that name.
+    pub fn str(&self, name: &str) -> &'a str {
+        match self.captures.name(name) {
+            Some(field) => field.as_str(),
+            None if self.pattern.regex.capture_names().any(|group| group == Some(name)) => &self.snippet[..0],
+            None => panic!("The pattern for '{}' has no group '{}'", self.pattern.expected, name),
+        }
+    }
+
+    pub fn number<T: FromStr>(&self, name: &str) -> Result<T, ParseError> {
+        number(self.str(name))
+    }
+
+    /// The numbers in a field, separated by `separator` and optional whitespace.
+    pub fn numbers<T: FromStr>(&self, name: &str, separator: &str) -> Result<Vec<T>, ParseError> {
+        let field = self.str(name);
+        if field.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        field.split(separator).map(|item| number(item.trim())).collect()
+    }
+}
@@ -0,0 +1,234 @@
+//! Pictures of grids: coloured text for the terminal and image files.
+//!
+//! A picture is a [`Grid`] of [`Colour`]s, usually made with [`Grid::map`] from the grid a
+//! day works on. Images are written without compression, which keeps them simple but big.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+use crate::simulation::{Driver, Simulation};
+
+/// The pictures of a part of a day, see [`crate::solution::Solution::pictures`].
+pub type Pictures = Vec<Grid<Colour>>;
+
+/// How many pictures [`frames`] draws of a simulation at most, besides the first one.
+pub const FRAMES: usize = 100;
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::rgb(0, 0, 0);
+    pub const WHITE: Colour = Colour::rgb(255, 255, 255);
+    pub const GREY: Colour = Colour::rgb(128, 128, 128);
+    pub const RED: Colour = Colour::rgb(220, 50, 47);
+    pub const GREEN: Colour = Colour::rgb(64, 160, 43);
+    pub const BLUE: Colour = Colour::rgb(38, 139, 210);
+    pub const YELLOW: Colour = Colour::rgb(230, 190, 40);
+    pub const BROWN: Colour = Colour::rgb(120, 80, 50);
+
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Colour { red, green, blue }
+    }
+
+    /// The colour `fraction` of the way from `self` to `other`, `fraction` is clamped to 0..=1.
+    pub fn blend(self, other: Colour, fraction: f64) -> Colour {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * fraction).round() as u8;
+        Colour::rgb(mix(self.red, other.red), mix(self.green, other.green), mix(self.blue, other.blue))
+    }
+}
+
+/// Draws the grid as text with one coloured character per cell, using 24-bit ANSI colours.
+pub fn ansi<T>(grid: &Grid<T>, mut cell: impl FnMut(&T) -> (char, Colour)) -> String {
+    let mut text = String::new();
+    for y in 0..grid.height() {
+        let mut current = None;
+        for value in grid.row(y) {
+            let (c, colour) = cell(value);
+            if current != Some(colour) {
+                write!(text, "\x1b[38;2;{};{};{}m", colour.red, colour.green, colour.blue).unwrap();
+                current = Some(colour);
+            }
+            text.push(c);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// Pictures of a simulation from its start to its end, evenly apart and at most [`FRAMES`]
+/// after the first one. The simulation runs twice, first to count its steps.
+pub fn frames<S, M, P>(make: M, picture: P) -> Pictures
+    where S: Simulation, M: Fn() -> S, P: Fn(&S) -> Grid<Colour> {
+    let mut driver = Driver::new(make());
+    driver.run();
+    let every = driver.steps().div_ceil(FRAMES).max(1);
+
+    let mut driver = Driver::new(make());
+    let mut pictures = vec![picture(driver.simulation())];
+    while driver.run_steps(every) > 0 {
+        pictures.push(picture(driver.simulation()));
+    }
+    pictures
+}
+
+/// The scale at which the picture fills `pixels` by `pixels` best, between 1 and 8.
+pub fn fitting_scale(picture: &Grid<Colour>, pixels: usize) -> usize {
+    (pixels / picture.width().max(picture.height()).max(1)).clamp(1, 8)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ImageFormat {
+    PPM,
+    PNG,
+}
+
+impl ImageFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ppm" => Some(ImageFormat::PPM),
+            "png" => Some(ImageFormat::PNG),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::PPM => "ppm",
+            ImageFormat::PNG => "png",
+        }
+    }
+
+    /// The image file with every cell drawn as a square of `scale` by `scale` pixels.
+    pub fn encode(self, picture: &Grid<Colour>, scale: usize) -> Vec<u8> {
+        match self {
+            ImageFormat::PPM => ppm(picture, scale),
+            ImageFormat::PNG => png(picture, scale),
+        }
+    }
+}
+
+/// The rows of pixels of the picture, every cell repeated `scale` times in both directions.
+fn scanlines(picture: &Grid<Colour>, scale: usize) -> impl Iterator<Item=Vec<u8>> + '_ {
+    (0..picture.height() * scale).map(move |y| {
+        picture.row(y / scale)
+            .flat_map(|colour| [colour.red, colour.green, colour.blue].repeat(scale))
+            .collect()
+    })
+}
+
+/// A binary PPM image (`P6`).
+pub fn ppm(picture: &Grid<Colour>, scale: usize) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", picture.width() * scale, picture.height() * scale).into_bytes();
+    for line in scanlines(picture, scale) {
+        image.extend(line);
+    }
+    image
+}
+
+/// A PNG image with 8 bit RGB pixels, stored without compression.
+pub fn png(picture: &Grid<Colour>, scale: usize) -> Vec<u8> {
+    let mut pixels = Vec::new();
+    for line in scanlines(picture, scale) {
+        // Every line starts with its filter type, 0 is none.
+        pixels.push(0);
+        pixels.extend(line);
+    }
+
+    let mut header = Vec::new();
+    header.extend(((picture.width() * scale) as u32).to_be_bytes());
+    header.extend(((picture.height() * scale) as u32).to_be_bytes());
+    // Bit depth 8, colour type RGB, default compression and filters, no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut image = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    png_chunk(&mut image, b"IHDR", &header);
+    png_chunk(&mut image, b"IDAT", &zlib_stored(&pixels));
+    png_chunk(&mut image, b"IEND", &[]);
+    image
+}
+
+fn png_chunk(image: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    image.extend((data.len() as u32).to_be_bytes());
+    let start = image.len();
+    image.extend(kind);
+    image.extend(data);
+    let crc = crc32(&image[start..]);
+    image.extend(crc.to_be_bytes());
+}
+
+/// A zlib stream of deflate blocks that are stored as they are.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(last as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Writes pictures as numbered image files `frame00000.png`, `frame00001.png`, ... into
+/// a directory, for example to turn them into an animation with another tool.
+pub struct Frames {
+    directory: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    written: usize,
+}
+
+impl Frames {
+    /// Creates the directory if it doesn't exist yet.
+    pub fn create(directory: &Path, format: ImageFormat, scale: usize) -> io::Result<Self> {
+        fs::create_dir_all(directory)?;
+        Ok(Frames { directory: directory.to_path_buf(), format, scale: scale.max(1), written: 0 })
+    }
+
+    /// Writes the next frame and returns its path.
+    pub fn write(&mut self, picture: &Grid<Colour>) -> io::Result<PathBuf> {
+        let file = self.directory.join(format!("frame{:05}.{}", self.written, self.format.extension()));
+        fs::write(&file, self.format.encode(picture, self.scale))?;
+        self.written += 1;
+        Ok(file)
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+}
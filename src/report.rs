@@ -0,0 +1,151 @@
+//! Output of outcomes, as a table of the answers to several inputs or machine readable.
+//! Both machine readable formats have the same fields in the same order:
+//!
+//! | field        | content                                                             |
+//! |--------------|---------------------------------------------------------------------|
+//! | `day`        | number of the day                                                   |
+//! | `part`       | 1 or 2                                                              |
+//! | `input`      | path of the input file, `<stdin>` or `<inline>`                     |
+//! | `answer`     | the answer as text, empty/`null` if the part failed                 |
+//! | `elapsed_ns` | nanoseconds to solve the part, for the first part including parsing |
+//! | `error`      | why the part failed, empty/`null` if it succeeded                   |
+
+use std::fmt::Write as _;
+
+use crate::error::Error;
+use crate::files;
+use crate::runner::Outcome;
+
+pub const FIELDS: [&str; 6] = ["day", "part", "input", "answer", "elapsed_ns", "error"];
+
+/// A JSON array with one object per outcome.
+pub fn json(outcomes: &[Outcome]) -> String {
+    let mut text = String::from("[");
+    for (index, outcome) in outcomes.iter().enumerate() {
+        let (answer, error) = match &outcome.result {
+            Ok(answer) => (json_string(&answer.to_string()), "null".to_string()),
+            Err(error) => ("null".to_string(), json_string(&error.to_string())),
+        };
+        let values = [
+            outcome.day.to_string(),
+            outcome.part.number().to_string(),
+            json_string(&outcome.input),
+            answer,
+            outcome.elapsed.as_nanos().to_string(),
+            error,
+        ];
+        let fields = FIELDS.iter().zip(values)
+            .map(|(field, value)| format!("\"{}\": {}", field, value))
+            .collect::<Vec<String>>();
+        let separator = if index == 0 { "" } else { "," };
+        write!(text, "{}\n  {{{}}}", separator, fields.join(", ")).unwrap();
+    }
+    text.push_str("\n]");
+    text
+}
+
+/// `string` as a JSON string literal, in quotes and escaped.
+pub fn json_string(string: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// CSV with a header line, fields are quoted as described in RFC 4180.
+pub fn csv(outcomes: &[Outcome]) -> String {
+    let mut text = FIELDS.join(",");
+    for outcome in outcomes {
+        let (answer, error) = match &outcome.result {
+            Ok(answer) => (answer.to_string(), String::new()),
+            Err(error) => (String::new(), error.to_string()),
+        };
+        let values = [
+            outcome.day.to_string(),
+            outcome.part.number().to_string(),
+            csv_field(&outcome.input),
+            csv_field(&answer),
+            outcome.elapsed.as_nanos().to_string(),
+            csv_field(&error),
+        ];
+        write!(text, "\n{}", values.join(",")).unwrap();
+    }
+    text
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The answers to several inputs side by side, one column per input and one row per day
+/// and part. Every column has the outcomes of the same days and parts in the same order.
+/// Answers over several lines take as many lines of the table. Errors over several lines,
+/// like those pointing into the input, only show their first line in the table and follow
+/// it in full.
+pub fn table(inputs: &[String], columns: &[Vec<Outcome>]) -> String {
+    let mut rows = vec![row(String::new(), inputs.iter().map(|input| vec![input.clone()]).collect())];
+    let mut errors = String::new();
+    if let Some(first) = columns.first() {
+        for (index, outcome) in first.iter().enumerate() {
+            let label = format!("Day {:02} Part {}", outcome.day, outcome.part.number());
+            let cells = columns.iter().map(|column| cell(&column[index])).collect();
+            for (input, column) in inputs.iter().zip(columns) {
+                if let Err(error) = &column[index].result {
+                    let message = error.to_string();
+                    if message.contains('\n') {
+                        write!(errors, "\n{} ({}): error: {}\n", label, input, message).unwrap();
+                    }
+                }
+            }
+            rows.push(row(label, cells));
+        }
+    }
+
+    let widths: Vec<usize> = (0..=inputs.len())
+        .map(|column| rows.iter().flatten().map(|line| line[column].chars().count()).max().unwrap_or(0))
+        .collect();
+    let mut text = String::new();
+    for line in rows.iter().flatten() {
+        let padded: Vec<String> = line.iter().zip(&widths)
+            .map(|(field, width)| format!("{:<width$}", field, width = width))
+            .collect();
+        writeln!(text, "{}", padded.join("  ").trim_end()).unwrap();
+    }
+    text.push_str(&errors);
+    text
+}
+
+fn cell(outcome: &Outcome) -> Vec<String> {
+    match &outcome.result {
+        Ok(answer) => answer.to_string().lines().map(str::to_string).collect(),
+        Err(Error::Input(files::Error::NotFound(_))) => vec!["-".to_string()],
+        Err(error) => vec![format!("error: {}", error.to_string().lines().next().unwrap_or_default())],
+    }
+}
+
+/// The lines of a row of the table, each with the label and one field per column.
+fn row(label: String, cells: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    let height = cells.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    (0..height)
+        .map(|line| {
+            let label = if line == 0 { label.clone() } else { String::new() };
+            std::iter::once(label)
+                .chain(cells.iter().map(|cell| cell.get(line).cloned().unwrap_or_default()))
+                .collect()
+        })
+        .collect()
+}
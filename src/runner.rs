@@ -0,0 +1,412 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13, day14, day15};
+use crate::bench::{self, Timings};
+use crate::error::Error;
+use crate::files;
+use crate::parameters::{Override, Parameter, Parameters};
+use crate::render::Pictures;
+use crate::solution::{self, Answer, Part, Solution};
+use crate::trace::{self, Level};
+
+/// How long a solver may run unless told otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Some solvers recurse deeply, e.g. through the directories of day 7 or the packets of day 13.
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Starts the names of the worker threads, whose panics are reported by [`solve_guarded`].
+const WORKER_PREFIX: &str = "solver-";
+
+static PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// The message of the last panic of a worker thread, kept by the panic hook.
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The directory of the puzzle inputs, the example inputs and the profiles.
+pub const RESOURCES: &str = "resources";
+
+pub struct Day {
+    pub number: u8,
+    pub parameters: &'static [Parameter],
+    solve: fn(&str, &[Part], &Parameters, solution::Report),
+    measure: fn(&str, &[Part], &Parameters, usize) -> Result<Timings, Error>,
+    pictures: fn(&str, Part, &Parameters) -> Result<Pictures, Error>,
+}
+
+impl Day {
+    /// The day solved by `S`, which doesn't have to be one of the registered days.
+    pub const fn new<S: Solution>(number: u8) -> Self {
+        Day { number, parameters: S::PARAMETERS, solve: solution::solve::<S>, measure: bench::measure::<S>, pictures: solution::pictures::<S> }
+    }
+
+    /// Solves one part of this day for the given puzzle text.
+    pub fn solve(&self, input: &str, part: Part, parameters: &Parameters) -> Result<Answer, Error> {
+        let mut answer = Err(Error::no_answer("The part was not solved"));
+        (self.solve)(input, &[part], parameters, &mut |_, result| answer = result);
+        answer
+    }
+
+    /// Solves the parts with the input parsed once, see [`solution::solve`].
+    pub fn solve_parts(&self, input: &str, parts: &[Part], parameters: &Parameters, report: solution::Report) {
+        (self.solve)(input, parts, parameters, report)
+    }
+
+    /// Times parsing and the given parts over several runs, see [`bench::measure`].
+    pub fn measure(&self, input: &str, parts: &[Part], parameters: &Parameters, runs: usize) -> Result<Timings, Error> {
+        (self.measure)(input, parts, parameters, runs)
+    }
+
+    /// Draws the part for the given puzzle text, see [`Solution::pictures`].
+    pub fn pictures(&self, input: &str, part: Part, parameters: &Parameters) -> Result<Pictures, Error> {
+        (self.pictures)(input, part, parameters)
+    }
+
+    /// The parameter values for an example or a real input with the overrides applied.
+    pub fn resolve_parameters(&self, example: bool, overrides: &[Override]) -> Parameters {
+        Parameters::resolve(self.number, self.parameters, example, overrides)
+    }
+
+    pub fn has_parameter(&self, name: &str) -> bool {
+        self.parameters.iter().any(|parameter| parameter.name == name)
+    }
+
+    pub fn input_file(&self) -> PathBuf {
+        PathBuf::from(format!("{}/day{:02}.txt", RESOURCES, self.number))
+    }
+
+    /// The puzzle input of the day in the directory of the profile.
+    pub fn profile_file(&self, profile: &str) -> PathBuf {
+        PathBuf::from(format!("{}/{}/day{:02}.txt", RESOURCES, profile, self.number))
+    }
+
+    /// Examples are shared by all profiles.
+    pub fn example_file(&self) -> PathBuf {
+        PathBuf::from(format!("{}/day{:02}-test.txt", RESOURCES, self.number))
+    }
+}
+
+/// Where the puzzle input of a day is taken from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    /// The day's own puzzle input `resources/dayNN.txt`.
+    Puzzle,
+    /// The day's example input `resources/dayNN-test.txt`.
+    Example,
+    /// The day's puzzle input of a profile, `resources/<profile>/dayNN.txt`.
+    Profile(String),
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// Whether the source names one concrete input rather than one per day.
+    pub fn is_explicit(&self) -> bool {
+        !matches!(self, InputSource::Puzzle | InputSource::Example | InputSource::Profile(_))
+    }
+
+    /// Whether the source is an example input, which is solved with the example parameters.
+    pub fn is_example(&self) -> bool {
+        match self {
+            InputSource::Example => true,
+            InputSource::File(path) => is_example_file(path),
+            _ => false,
+        }
+    }
+
+    /// The file the input is read from, if it is a file.
+    pub fn file(&self, day: &Day) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(day.input_file()),
+            InputSource::Example => Some(day.example_file()),
+            InputSource::Profile(profile) => Some(day.profile_file(profile)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin | InputSource::Inline(_) => None,
+        }
+    }
+
+    /// Names the input in reports, e.g. `resources/day15.txt` or `<stdin>`.
+    pub fn describe(&self, day: &Day) -> String {
+        match self {
+            InputSource::Puzzle => day.input_file().display().to_string(),
+            InputSource::Example => day.example_file().display().to_string(),
+            InputSource::Profile(profile) => day.profile_file(profile).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+            InputSource::Inline(_) => "<inline>".to_string(),
+        }
+    }
+
+    pub fn read(&self, day: &Day) -> Result<String, files::Error> {
+        match self {
+            InputSource::Puzzle => files::parse_string_from(day.input_file()),
+            InputSource::Example => files::parse_string_from(day.example_file()),
+            InputSource::Profile(profile) => files::parse_string_from(day.profile_file(profile)),
+            InputSource::File(path) => files::parse_string_from(path),
+            InputSource::Stdin => files::parse_string_from_stdin(),
+            InputSource::Inline(input) => Ok(input.clone()),
+        }
+    }
+}
+
+/// The profiles with their own puzzle inputs, the directories in `resources/`, sorted by name.
+pub fn profiles() -> Result<Vec<String>, String> {
+    let entries = match fs::read_dir(RESOURCES) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(format!("Could not read {}: {}", RESOURCES, error)),
+    };
+    let mut profiles = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|error| format!("Could not read {}: {}", RESOURCES, error))?;
+        if entry.path().is_dir() {
+            profiles.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
+/// Example inputs are named like `day15-test.txt`.
+pub fn is_example_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name.to_string_lossy().ends_with("-test.txt"))
+}
+
+pub const DAYS: [Day; 15] = [
+    Day::new::<day01::Day01>(1),
+    Day::new::<day02::Day02>(2),
+    Day::new::<day03::Day03>(3),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day05::Day05>(5),
+    Day::new::<day06::Day06>(6),
+    Day::new::<day07::Day07>(7),
+    Day::new::<day08::Day08>(8),
+    Day::new::<day09::Day09>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+pub struct Outcome {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    /// The time the part took, for the first part of a day including parsing the input.
+    pub elapsed: Duration,
+    pub result: Result<Answer, Error>,
+}
+
+impl Outcome {
+    pub fn succeeded(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Runs the selected parts of every given day and hands each outcome to `report` as soon
+/// as it is known. A panicking solver is recorded as failed and does not stop the remaining
+/// days from running.
+pub fn run<F>(days: &[&Day], parts: &[Part], source: &InputSource, overrides: &[Override], timeout: Option<Duration>, mut report: F)
+    -> Vec<Outcome> where F: FnMut(&Outcome) {
+    let mut outcomes = Vec::new();
+    for day in days {
+        let description = source.describe(day);
+        let input: Arc<str> = match source.read(day) {
+            Ok(input) => input.into(),
+            Err(error) => {
+                for part in parts {
+                    let result = Err(Error::Input(error.clone()));
+                    let outcome = Outcome { day: day.number, part: *part, input: description.clone(), elapsed: Duration::ZERO, result };
+                    report(&outcome);
+                    outcomes.push(outcome);
+                }
+                continue;
+            }
+        };
+        let parameters = day.resolve_parameters(source.is_example(), overrides);
+        solve_parts_guarded(day, &input, parts, &parameters, timeout, |part, result, elapsed| {
+            let outcome = Outcome { day: day.number, part, input: description.clone(), elapsed, result };
+            report(&outcome);
+            outcomes.push(outcome);
+        });
+    }
+    outcomes
+}
+
+/// Solves one part, see [`solve_parts_guarded`].
+pub fn solve_guarded(day: &Day, input: &Arc<str>, part: Part, parameters: &Parameters, timeout: Option<Duration>) -> Result<Answer, Error> {
+    let mut answer = Err(worker_stopped());
+    solve_parts_guarded(day, input, &[part], parameters, timeout, |_, result, _| answer = result);
+    answer
+}
+
+/// Solves the parts on a worker thread, which parses the input once for all of them, so
+/// that neither a panic nor a solver that runs for too long stops the caller. Each result
+/// goes to `report` with the time it took, which for the first part includes parsing.
+///
+/// A thread can't be stopped, so a solver that timed out keeps running in the background
+/// until it is done or the program exits. The parts after it, or after a panic, are solved
+/// by a new worker.
+pub fn solve_parts_guarded<F>(day: &Day, input: &Arc<str>, parts: &[Part], parameters: &Parameters, timeout: Option<Duration>, mut report: F)
+    where F: FnMut(Part, Result<Answer, Error>, Duration) {
+    install_panic_hook();
+    let mut remaining = parts;
+    while !remaining.is_empty() {
+        let receiver = start_worker(day, input, remaining, parameters);
+        let (mut solved, mut timed_out) = (0, false);
+        for part in remaining {
+            let start = Instant::now();
+            let received = match timeout {
+                Some(timeout) => receiver.recv_timeout(timeout).map_err(|error| match error {
+                    RecvTimeoutError::Timeout => Error::TimedOut(timeout),
+                    RecvTimeoutError::Disconnected => worker_stopped(),
+                }),
+                None => receiver.recv().map_err(|_| worker_stopped()),
+            };
+            let result = received.and_then(|result| result);
+            let stopped = matches!(result, Err(Error::TimedOut(_) | Error::Panicked(_)));
+            timed_out = matches!(result, Err(Error::TimedOut(_)));
+            report(*part, result, start.elapsed());
+            solved += 1;
+            if stopped {
+                break;
+            }
+        }
+        if !timed_out {
+            // Hangs up right after ending its trace of the day, which the caller may be
+            // about to write out.
+            let _ = receiver.recv();
+        }
+        remaining = &remaining[solved..];
+    }
+}
+
+/// Starts a worker thread solving the parts, which sends the result of each part and stops
+/// after a panic.
+fn start_worker(day: &Day, input: &Arc<str>, parts: &[Part], parameters: &Parameters) -> Receiver<Result<Answer, Error>> {
+    let (number, solve, input, parts, parameters) = (day.number, day.solve, Arc::clone(input), parts.to_vec(), parameters.clone());
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(format!("{}day{:02}", WORKER_PREFIX, number))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            let span = trace::span(Level::INFO, format!("day{:02}", number));
+            let solved = Cell::new(0);
+            let send = |part: Part, result: Result<Answer, Error>| {
+                if trace::enabled(Level::INFO) {
+                    let part = ("part", part.number().into());
+                    match &result {
+                        Ok(answer) => trace::event(Level::INFO, "answer", &[part, ("answer", answer.to_string().into())]),
+                        Err(error) => trace::event(Level::INFO, "failed", &[part, ("error", error.to_string().into())]),
+                    }
+                }
+                solved.set(solved.get() + 1);
+                // Nobody listens any more once a part timed out.
+                let _ = sender.send(result);
+            };
+            let finished = panic::catch_unwind(AssertUnwindSafe(|| solve(&input, &parts, &parameters, &mut |part, result| send(part, result))));
+            if let Err(payload) = finished {
+                let message = PANIC.with(|panic| panic.borrow_mut().take()).unwrap_or_else(|| panic_message(&*payload));
+                send(parts[solved.get()], Err(Error::Panicked(message)));
+            }
+            drop(span);
+            drop(sender);
+        })
+        .expect("Could not start a worker thread");
+    receiver
+}
+
+/// Times the parts like [`Day::measure`] on a worker thread, so that neither a panic nor a
+/// solver that runs for too long stops the caller. Each run may take up to `timeout`, the
+/// measurement as a whole gives up after `timeout` times `runs`.
+pub fn measure_guarded(day: &Day, input: &Arc<str>, parts: &[Part], parameters: &Parameters, runs: usize, timeout: Option<Duration>)
+    -> Result<Timings, Error> {
+    install_panic_hook();
+    let (measure, input, parts, parameters) = (day.measure, Arc::clone(input), parts.to_vec(), parameters.clone());
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(format!("{}day{:02}", WORKER_PREFIX, day.number))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            let timings = panic::catch_unwind(|| measure(&input, &parts, &parameters, runs)).unwrap_or_else(|payload| {
+                let message = PANIC.with(|panic| panic.borrow_mut().take()).unwrap_or_else(|| panic_message(&*payload));
+                Err(Error::Panicked(message))
+            });
+            // Nobody listens any more once the measurement timed out.
+            let _ = sender.send(timings);
+        })
+        .expect("Could not start a worker thread");
+    match timeout.map(|timeout| timeout.saturating_mul(runs.try_into().unwrap_or(u32::MAX))) {
+        Some(timeout) => receiver.recv_timeout(timeout).unwrap_or_else(|error| match error {
+            RecvTimeoutError::Timeout => Err(Error::TimedOut(timeout)),
+            RecvTimeoutError::Disconnected => Err(worker_stopped()),
+        }),
+        None => receiver.recv().unwrap_or_else(|_| Err(worker_stopped())),
+    }
+}
+
+fn worker_stopped() -> Error {
+    Error::Panicked("the worker stopped without an answer".to_string())
+}
+
+pub fn print_outcome(outcome: &Outcome) {
+    let label = format!("Day {:02} Part {}", outcome.day, outcome.part.number());
+    match &outcome.result {
+        Ok(Answer::Text(text)) if text.contains('\n') => println!("{}:\n{}", label, text),
+        Ok(answer) => println!("{}: {}", label, answer),
+        Err(error) => println!("{}: {}", label, error),
+    }
+}
+
+/// Replaces the panic hook once, so that the panics of worker threads are kept for their
+/// outcome instead of being printed. Other threads panic like before.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name().is_some_and(|name| name.starts_with(WORKER_PREFIX)) {
+                let message = panic_message(info.payload());
+                PANIC.with(|panic| *panic.borrow_mut() = Some(message));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown reason".to_string()
+    }
+}
+
+pub fn print_summary(outcomes: &[Outcome]) {
+    println!();
+    println!("Summary:");
+    for outcome in outcomes {
+        let status = if outcome.succeeded() { "ok" } else { "FAILED" };
+        println!("  Day {:02} Part {}: {:<6} ({:.2?})", outcome.day, outcome.part.number(), status, outcome.elapsed);
+    }
+}
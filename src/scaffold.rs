@@ -0,0 +1,210 @@
+//! Creates the files of a new day and registers the day everywhere the days are listed,
+//! so that it can be run right away:
+//!
+//! - `src/dayNN.rs`, a [`Solution`](crate::solution::Solution) without answers yet,
+//! - empty `resources/dayNN.txt` and `resources/dayNN-test.txt`,
+//! - `pub mod dayNN;` in `src/lib.rs` and the day in [`DAYS`](crate::runner::DAYS),
+//! - a generator of random lines in `src/generate.rs`, to be replaced by one that makes
+//!   inputs like those of the puzzle,
+//! - ignored regression tests of both inputs, with placeholder answers in
+//!   `resources/answers.txt`.
+//!
+//! Nothing is written if any of the new files exists already or the day is registered.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LIB: &str = "src/lib.rs";
+const RUNNER: &str = "src/runner.rs";
+const GENERATE: &str = "src/generate.rs";
+const REGRESSION: &str = "tests/regression.rs";
+const ANSWERS: &str = "resources/answers.txt";
+
+/// Adds a new day to the lines of a file the days are listed in.
+type Registration = fn(&mut Vec<String>, u8) -> Result<(), String>;
+
+/// The answer in the manifest until the real one is known.
+const PLACEHOLDER: &str = "?";
+
+const MODULE_TEMPLATE: &str = "\
+use crate::error::Error;
+use crate::parameters::Parameters;
+use crate::solution::{Answer, Solution};
+
+pub struct Day{NN};
+
+impl Solution for Day{NN} {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(_input: &Self::Input, _parameters: &Parameters) -> Result<Answer, Error> {
+        Err(Error::no_answer(\"Part 1 is not solved yet\"))
+    }
+
+    fn part2(_input: &Self::Input, _parameters: &Parameters) -> Result<Answer, Error> {
+        Err(Error::no_answer(\"Part 2 is not solved yet\"))
+    }
+}
+";
+
+const GENERATOR_TEMPLATE: &str = "\
+fn day{NN}(random: &mut Random, lines: usize) -> String {
+    (0..lines).map(|_| format!(\"{}\\n\", random.range(0..=100))).collect()
+}";
+
+/// The files a new day consists of, relative to the root of the repository.
+pub fn new_files(day: u8) -> [PathBuf; 3] {
+    [
+        PathBuf::from(format!("src/day{:02}.rs", day)),
+        PathBuf::from(format!("resources/day{:02}.txt", day)),
+        PathBuf::from(format!("resources/day{:02}-test.txt", day)),
+    ]
+}
+
+/// Creates and registers the day in the repository at `root`. Returns the files written,
+/// the new ones followed by the changed ones.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {} in Advent of Code", day));
+    }
+    let registrations: [(&str, Registration); 5] = [
+        (LIB, register_module),
+        (RUNNER, register_day),
+        (GENERATE, register_generator),
+        (REGRESSION, register_tests),
+        (ANSWERS, register_answers),
+    ];
+    let mut changed = Vec::new();
+    for (file, register) in registrations {
+        let text = fs::read_to_string(root.join(file))
+            .map_err(|error| format!("Could not read {}: {}, is {} the root of the repository?", file, error, root.display()))?;
+        let mut lines = text.lines().map(str::to_string).collect();
+        register(&mut lines, day).map_err(|error| format!("{}: {}", file, error))?;
+        changed.push((PathBuf::from(file), lines.join("\n") + "\n"));
+    }
+
+    let new_files = new_files(day);
+    if let Some(existing) = new_files.iter().find(|file| root.join(file).exists()) {
+        return Err(format!("{} exists already, not overwriting anything", existing.display()));
+    }
+    let module = MODULE_TEMPLATE.replace("{NN}", &format!("{:02}", day));
+    let created = new_files.into_iter().zip([module, String::new(), String::new()]);
+
+    let mut written = Vec::new();
+    for (file, text) in created.chain(changed) {
+        fs::write(root.join(&file), text).map_err(|error| format!("Could not write {}: {}", file.display(), error))?;
+        written.push(file);
+    }
+    Ok(written)
+}
+
+/// Inserts `new` before the first line in `lines[start..end]` that belongs to a later day,
+/// or at `end`. `number` tells the day a line belongs to, if any.
+fn insert_in_order<F>(lines: &mut Vec<String>, start: usize, end: usize, day: u8, number: F, new: &[String]) -> Result<(), String>
+    where F: Fn(&str) -> Option<u8> {
+    let mut index = end;
+    for (offset, line) in lines[start..end].iter().enumerate() {
+        match number(line) {
+            Some(number) if number == day => return Err(format!("day {} is there already", day)),
+            Some(number) if number > day && index == end => index = start + offset,
+            _ => {}
+        }
+    }
+    lines.splice(index..index, new.iter().cloned());
+    Ok(())
+}
+
+/// The first line after `start` that satisfies the predicate.
+fn find(lines: &[String], start: usize, what: &str, predicate: impl Fn(&str) -> bool) -> Result<usize, String> {
+    lines.iter().skip(start).position(|line| predicate(line))
+        .map(|offset| start + offset)
+        .ok_or_else(|| format!("could not find {}", what))
+}
+
+/// The number of `digits` if it is a two digit number, like `04` in `day04`.
+fn two_digits(digits: &str) -> Option<u8> {
+    if digits.len() == 2 { digits.parse().ok() } else { None }
+}
+
+/// `pub mod dayNN;` with the other days.
+fn register_module(lines: &mut Vec<String>, day: u8) -> Result<(), String> {
+    let number = |line: &str| two_digits(line.strip_prefix("pub mod day")?.strip_suffix(';')?);
+    let start = find(lines, 0, "the day modules", |line| number(line).is_some())?;
+    let end = lines.iter().rposition(|line| number(line).is_some()).unwrap() + 1;
+    insert_in_order(lines, start, end, day, number, &[format!("pub mod day{:02};", day)])
+}
+
+/// The module in the imports of the days and the day in `DAYS`.
+fn register_day(lines: &mut Vec<String>, day: u8) -> Result<(), String> {
+    let imports = find(lines, 0, "the import of the days", |line| line.starts_with("use crate::{day"))?;
+    let mut modules: Vec<&str> = lines[imports].trim_start_matches("use crate::{").trim_end_matches("};")
+        .split(", ").collect();
+    let module = format!("day{:02}", day);
+    if modules.contains(&module.as_str()) {
+        return Err(format!("day {} is there already", day));
+    }
+    modules.push(&module);
+    modules.sort();
+    lines[imports] = format!("use crate::{{{}}};", modules.join(", "));
+
+    const DAYS: &str = "pub const DAYS: [Day; ";
+    let start = find(lines, 0, "DAYS", |line| line.starts_with(DAYS))?;
+    let count = lines[start].trim_start_matches(DAYS).trim_end_matches("] = [").parse::<usize>()
+        .map_err(|_| "could not find the number of DAYS".to_string())?;
+    lines[start] = format!("{}{}] = [", DAYS, count + 1);
+    let end = find(lines, start, "the end of DAYS", |line| line == "];")?;
+    let number = |line: &str| line.trim().strip_prefix("Day::new::<")?.split_once(">(")?.1.strip_suffix("),")?.parse().ok();
+    insert_in_order(lines, start + 1, end, day, number, &[format!("    Day::new::<day{:02}::Day{:02}>({}),", day, day, day)])
+}
+
+/// The generator function and its case in `input`.
+fn register_generator(lines: &mut Vec<String>, day: u8) -> Result<(), String> {
+    let start = find(lines, 0, "the generators", |line| line.starts_with("pub fn input("))?;
+    let end = find(lines, start, "the end of the generators", |line| line.trim() == "_ => return None,")?;
+    let number = |line: &str| line.trim().split_once(" => day")?.0.parse().ok();
+    insert_in_order(lines, start, end, day, number, &[format!("        {} => day{:02},", day, day)])?;
+
+    let number = |line: &str| two_digits(line.strip_prefix("fn day")?.get(..2)?);
+    let generator = GENERATOR_TEMPLATE.replace("{NN}", &format!("{:02}", day));
+    let generator = generator.lines().map(str::to_string);
+    match lines.iter().position(|line| number(line).is_some_and(|number| number > day)) {
+        Some(next) => {
+            lines.splice(next..next, generator.chain([String::new()]));
+        }
+        None => {
+            lines.push(String::new());
+            lines.extend(generator);
+        }
+    }
+    Ok(())
+}
+
+/// Ignored regression tests of the example and the puzzle input.
+fn register_tests(lines: &mut Vec<String>, day: u8) -> Result<(), String> {
+    let start = find(lines, 0, "the regression tests", |line| line == "regression_tests! {")?;
+    let end = find(lines, start, "the end of the regression tests", |line| line == "}")?;
+    let number = |line: &str| {
+        let test = line.trim();
+        let test = test.rsplit_once("] ").map_or(test, |(_, test)| test);
+        two_digits(test.strip_prefix("day")?.get(..2)?)
+    };
+    let ignore = format!("#[ignore = \"the answers in {} are placeholders\"]", ANSWERS);
+    let tests = [
+        format!("    {} day{:02}_example: \"day{:02}-test.txt\",", ignore, day, day),
+        format!("    {} day{:02}: \"day{:02}.txt\",", ignore, day, day),
+    ];
+    insert_in_order(lines, start + 1, end, day, number, &tests)
+}
+
+/// Placeholder answers for both inputs.
+fn register_answers(lines: &mut Vec<String>, day: u8) -> Result<(), String> {
+    let number = |line: &str| two_digits(line.strip_prefix("day")?.get(..2)?);
+    let end = lines.len();
+    let answers: Vec<String> = [format!("day{:02}-test.txt", day), format!("day{:02}.txt", day)].iter()
+        .flat_map(|file| [1, 2].map(|part| format!("{:<16}{} {}", file, part, PLACEHOLDER)))
+        .collect();
+    insert_in_order(lines, 0, end, day, number, &answers)
+}
@@ -0,0 +1,109 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Anything that can be searched: a map, a maze or the states of a puzzle.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes reachable in one step, each with the cost of that step.
+    /// [`bfs`] ignores the costs and counts the steps instead.
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+/// The first goal reached by a search.
+#[derive(Debug)]
+pub struct Found<N> {
+    pub goal: N,
+    /// Total cost of the cheapest path, or the number of steps for [`bfs`].
+    pub cost: u64,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash> Found<N> {
+    /// The nodes from the start to the goal, both included.
+    pub fn path(&self) -> Vec<N> {
+        let mut path = vec![self.goal.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search from all starts at once for the nearest node matching `is_goal`.
+/// Returns `None` if no goal can be reached.
+pub fn bfs<G, S, F>(graph: &G, starts: S, mut is_goal: F) -> Option<Found<G::Node>>
+    where G: Graph, S: IntoIterator<Item=G::Node>, F: FnMut(&G::Node) -> bool {
+    let mut predecessors: HashMap<G::Node, G::Node> = HashMap::new();
+    let mut distances: HashMap<G::Node, u64> = HashMap::new();
+    let mut next: VecDeque<G::Node> = VecDeque::new();
+    for start in starts {
+        if distances.insert(start.clone(), 0).is_none() {
+            next.push_back(start);
+        }
+    }
+
+    while let Some(current) = next.pop_front() {
+        let distance = distances[&current];
+        if is_goal(&current) {
+            return Some(Found { goal: current, cost: distance, predecessors });
+        }
+        for (neighbour, _) in graph.neighbours(&current) {
+            if distances.contains_key(&neighbour) {
+                continue;
+            }
+            distances.insert(neighbour.clone(), distance + 1);
+            predecessors.insert(neighbour.clone(), current.clone());
+            next.push_back(neighbour);
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm from all starts at once for the cheapest node matching `is_goal`.
+pub fn dijkstra<G, S, F>(graph: &G, starts: S, is_goal: F) -> Option<Found<G::Node>>
+    where G: Graph, S: IntoIterator<Item=G::Node>, F: FnMut(&G::Node) -> bool {
+    a_star(graph, starts, is_goal, |_| 0)
+}
+
+/// A* search, which explores the nodes in the order of their cost so far plus the
+/// `heuristic` estimate of the remaining cost. The result is the cheapest path as long as
+/// the heuristic never overestimates.
+pub fn a_star<G, S, F, H>(graph: &G, starts: S, mut is_goal: F, mut heuristic: H) -> Option<Found<G::Node>>
+    where G: Graph, S: IntoIterator<Item=G::Node>, F: FnMut(&G::Node) -> bool, H: FnMut(&G::Node) -> u64 {
+    let mut predecessors: HashMap<G::Node, G::Node> = HashMap::new();
+    let mut costs: HashMap<G::Node, u64> = HashMap::new();
+    // The heap holds (estimate, cost, node) and may contain outdated entries of nodes that
+    // were reached cheaper later on, those are skipped when popped.
+    let mut next: BinaryHeap<Reverse<(u64, u64, usize)>> = BinaryHeap::new();
+    let mut nodes: Vec<G::Node> = Vec::new();
+    for start in starts {
+        if costs.insert(start.clone(), 0).is_none() {
+            next.push(Reverse((heuristic(&start), 0, nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = next.pop() {
+        let current = nodes[index].clone();
+        if costs[&current] < cost {
+            continue;
+        }
+        if is_goal(&current) {
+            return Some(Found { goal: current, cost, predecessors });
+        }
+        for (neighbour, step) in graph.neighbours(&current) {
+            let cost = cost + step;
+            if costs.get(&neighbour).is_some_and(|known| *known <= cost) {
+                continue;
+            }
+            costs.insert(neighbour.clone(), cost);
+            predecessors.insert(neighbour.clone(), current.clone());
+            next.push(Reverse((cost + heuristic(&neighbour), cost, nodes.len())));
+            nodes.push(neighbour);
+        }
+    }
+    None
+}
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+/// Something that evolves step by step until it is done: a crane moving crates, a rope
+/// following its head or sand filling a cave.
+pub trait Simulation {
+    /// The state worth keeping after a step, as shown by [`Simulation::render`].
+    type Snapshot: Display;
+
+    /// Advances by one step. Does nothing once the simulation is done.
+    fn step(&mut self);
+
+    fn is_done(&self) -> bool;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Draws the current state.
+    fn render(&self) -> String {
+        self.snapshot().to_string()
+    }
+}
+
+/// Runs a simulation and counts its steps, optionally recording a snapshot before the
+/// first step and after every step.
+pub struct Driver<S: Simulation> {
+    simulation: S,
+    steps: usize,
+    recording: Option<Vec<S::Snapshot>>,
+}
+
+impl<S: Simulation> Driver<S> {
+    pub fn new(simulation: S) -> Self {
+        Driver { simulation, steps: 0, recording: None }
+    }
+
+    /// A driver that records snapshots for [`Driver::replay`].
+    pub fn recording(simulation: S) -> Self {
+        let recording = Some(vec![simulation.snapshot()]);
+        Driver { simulation, steps: 0, recording }
+    }
+
+    /// Runs until the simulation is done and returns how many steps this took, see
+    /// [`Driver::steps`] for all steps taken so far.
+    pub fn run(&mut self) -> usize {
+        let before = self.steps;
+        self.run_until(|_| false);
+        self.steps - before
+    }
+
+    /// Runs at most `steps` steps and returns how many were taken.
+    pub fn run_steps(&mut self, steps: usize) -> usize {
+        let before = self.steps;
+        while self.steps - before < steps && !self.simulation.is_done() {
+            self.step();
+        }
+        self.steps - before
+    }
+
+    /// Runs until the predicate holds or the simulation is done. Returns whether the
+    /// predicate holds, which is checked before the first step too.
+    pub fn run_until<P>(&mut self, mut predicate: P) -> bool where P: FnMut(&S) -> bool {
+        loop {
+            if predicate(&self.simulation) {
+                return true;
+            }
+            if self.simulation.is_done() {
+                return false;
+            }
+            self.step();
+        }
+    }
+
+    fn step(&mut self) {
+        self.simulation.step();
+        self.steps += 1;
+        if let Some(recording) = &mut self.recording {
+            recording.push(self.simulation.snapshot());
+        }
+    }
+
+    /// The number of steps taken so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+
+    pub fn into_simulation(self) -> S {
+        self.simulation
+    }
+
+    /// The recorded snapshots with the number of steps taken before each. Empty unless
+    /// the driver was created with [`Driver::recording`].
+    pub fn replay(&self) -> impl Iterator<Item=(usize, &S::Snapshot)> {
+        self.recording.iter().flatten().enumerate()
+    }
+}
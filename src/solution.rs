@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::error::Error;
+use crate::files;
+use crate::parameters::{Parameter, Parameters};
+use crate::render::Pictures;
+use crate::trace::{self, Level};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// The answer of a single puzzle part. Most puzzles are answered by a number,
+/// some (like the crate message of day 5 or the CRT screen of day 10) by text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Answer {
+                fn from(number: $number) -> Self {
+                    Answer::Number(i64::try_from(number).expect("Answer does not fit into an i64"))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// A puzzle of one day. The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    /// The constants from the puzzle text the parts can be tuned with.
+    const PARAMETERS: &'static [Parameter] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input, parameters: &Parameters) -> Result<Answer, Error>;
+
+    fn part2(input: &Self::Input, parameters: &Parameters) -> Result<Answer, Error>;
+
+    /// Draws the state of the puzzle while solving the part, the last picture showing
+    /// where it ends. Days that have nothing to show draw no pictures.
+    fn pictures(_input: &Self::Input, _part: Part, _parameters: &Parameters) -> Result<Pictures, Error> {
+        Ok(Vec::new())
+    }
+}
+
+/// A solver's way of handing over the answer to each part as soon as it is known.
+pub type Report<'a> = &'a mut dyn FnMut(Part, Result<Answer, Error>);
+
+/// Parses the input once and solves the parts with it, one after the other. Every part
+/// fails with the same error if the input can't be parsed.
+pub fn solve<S: Solution>(input: &str, parts: &[Part], parameters: &Parameters, report: Report) {
+    let input = match parse::<S>(input) {
+        Ok(input) => input,
+        Err(error) => {
+            for part in parts {
+                report(*part, Err(error.clone()));
+            }
+            return;
+        }
+    };
+    for part in parts {
+        let _span = trace::span(Level::DEBUG, match part {
+            Part::One => "part1",
+            Part::Two => "part2",
+        });
+        let answer = match part {
+            Part::One => S::part1(&input, parameters),
+            Part::Two => S::part2(&input, parameters),
+        };
+        report(*part, answer);
+    }
+}
+
+/// Parses the input and draws the part, see [`Solution::pictures`].
+pub fn pictures<S: Solution>(input: &str, part: Part, parameters: &Parameters) -> Result<Pictures, Error> {
+    S::pictures(&parse::<S>(input)?, part, parameters)
+}
+
+fn parse<S: Solution>(input: &str) -> Result<S::Input, Error> {
+    files::normalise(input).map_err(Error::from).and_then(|input| {
+        let _span = trace::span(Level::DEBUG, "parse");
+        S::parse(&input).map_err(|error| error.locate(&input))
+    })
+}
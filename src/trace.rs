@@ -0,0 +1,215 @@
+//! Tracing of what the solvers do, to find out why an answer is wrong without adding
+//! `println!`s. Solving a part is traced as nested spans, the day with `parse` and `part1`
+//! or `part2` inside it, and the days emit events of what they find on the way, down to
+//! every step of their simulations.
+//!
+//! Records go to stderr as text, or to a file as JSON lines with one object per record:
+//!
+//! | field     | content                                                         |
+//! |-----------|-----------------------------------------------------------------|
+//! | `time_us` | microseconds since tracing started                              |
+//! | `level`   | `INFO`, `DEBUG` or `TRACE`                                      |
+//! | `kind`    | `enter` or `exit` of a span, or `event`                         |
+//! | `span`    | the spans the record is in, outermost first, like `day05/part1` |
+//! | `name`    | name of the span or the event                                   |
+//! | `fields`  | object of the values of an event, `elapsed_us` for an exit      |
+
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use crate::report;
+
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// The days and their answers.
+    INFO = 1,
+    /// Parsing, the parts and what they compute on the way.
+    DEBUG,
+    /// Every step of the simulations.
+    TRACE,
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "info" => Some(Level::INFO),
+            "debug" => Some(Level::DEBUG),
+            "trace" => Some(Level::TRACE),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Level::INFO => "INFO",
+            Level::DEBUG => "DEBUG",
+            Level::TRACE => "TRACE",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    Number(i128),
+    Text(String),
+}
+
+macro_rules! value_from_number {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Value {
+                fn from(number: $number) -> Self {
+                    Value::Number(i128::from(number))
+                }
+            }
+        )*
+    };
+}
+
+value_from_number!(u8, i32, u32, i64, u64);
+
+impl From<usize> for Value {
+    fn from(number: usize) -> Self {
+        Value::Number(number as i128)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+struct Sink {
+    writer: Box<dyn Write + Send>,
+    json: bool,
+    start: Instant,
+}
+
+/// The most detailed level that is traced, 0 while nothing is.
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+thread_local! {
+    /// The names of the spans the current thread is in, outermost first.
+    static SPANS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Traces everything up to `level` as text to stderr.
+pub fn to_stderr(level: Level) {
+    install(level, Box::new(io::stderr()), false);
+}
+
+/// Traces everything up to `level` as JSON lines to `file`, replacing what it held.
+pub fn to_file(level: Level, file: &Path) -> Result<(), String> {
+    let writer = File::create(file).map_err(|error| format!("Could not create {}: {}", file.display(), error))?;
+    install(level, Box::new(BufWriter::new(writer)), true);
+    Ok(())
+}
+
+fn install(level: Level, writer: Box<dyn Write + Send>, json: bool) {
+    *SINK.lock().unwrap_or_else(PoisonError::into_inner) = Some(Sink { writer, json, start: Instant::now() });
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Stops tracing and writes out what is still buffered.
+pub fn finish() -> Result<(), String> {
+    LEVEL.store(0, Ordering::Relaxed);
+    match SINK.lock().unwrap_or_else(PoisonError::into_inner).take() {
+        Some(mut sink) => sink.writer.flush().map_err(|error| format!("Could not write the trace: {}", error)),
+        None => Ok(()),
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn event(level: Level, name: &str, fields: &[(&str, Value)]) {
+    if enabled(level) {
+        SPANS.with(|spans| write(level, "event", &spans.borrow(), name, fields, false));
+    }
+}
+
+/// Ends when dropped, with a record of how long it took.
+#[must_use = "a span ends as soon as it is dropped"]
+pub struct Span {
+    entered: Option<(Level, Instant)>,
+}
+
+/// Enters a span of the current thread if `level` is traced.
+pub fn span(level: Level, name: impl Into<String>) -> Span {
+    if !enabled(level) {
+        return Span { entered: None };
+    }
+    let name = name.into();
+    SPANS.with(|spans| {
+        write(level, "enter", &spans.borrow(), &name, &[], false);
+        spans.borrow_mut().push(name);
+    });
+    Span { entered: Some((level, Instant::now())) }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some((level, start)) = self.entered else { return };
+        let elapsed = Value::Number(start.elapsed().as_micros() as i128);
+        SPANS.with(|spans| {
+            let name = spans.borrow_mut().pop().unwrap_or_default();
+            // Flushed at the end of every span, so that the trace of a run that never ends
+            // or of a solver that timed out can be read up to there.
+            write(level, "exit", &spans.borrow(), &name, &[("elapsed_us", elapsed)], true);
+        });
+    }
+}
+
+fn write(level: Level, kind: &str, spans: &[String], name: &str, fields: &[(&str, Value)], flush: bool) {
+    let mut sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    let Some(sink) = sink.as_mut() else { return };
+    let time = sink.start.elapsed();
+    let span = spans.join("/");
+    let record = if sink.json {
+        let fields = fields.iter()
+            .map(|(key, value)| match value {
+                Value::Number(number) => format!("{}:{}", report::json_string(key), number),
+                Value::Text(text) => format!("{}:{}", report::json_string(key), report::json_string(text)),
+            })
+            .collect::<Vec<String>>();
+        format!("{{\"time_us\":{},\"level\":\"{}\",\"kind\":\"{}\",\"span\":{},\"name\":{},\"fields\":{{{}}}}}",
+                time.as_micros(), level, kind, report::json_string(&span), report::json_string(name), fields.join(","))
+    } else {
+        let marker = match kind {
+            "enter" => "> ",
+            "exit" => "< ",
+            _ => "",
+        };
+        let label = format!("{:>10.3?} {:<5} {}", time, level, span);
+        let mut text = format!("[{}] {}{}", label.trim_end(), marker, name);
+        for (key, value) in fields {
+            match value {
+                Value::Number(number) => text.push_str(&format!(" {}={}", key, number)),
+                Value::Text(value) => text.push_str(&format!(" {}={:?}", key, value)),
+            }
+        }
+        text
+    };
+    // A trace that can't be written must not fail the solver, it just ends early.
+    let _ = writeln!(sink.writer, "{}", record);
+    if flush {
+        let _ = sink.writer.flush();
+    }
+}
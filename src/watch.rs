@@ -0,0 +1,91 @@
+//! Re-running a day whenever one of its inputs changes. Files are polled for changes of
+//! their modification time or size, which needs nothing but the file system.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::runner::Outcome;
+
+/// What is known of a file to notice that it changed, `None` while it doesn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(file: &Path) -> Stamp {
+    let metadata = fs::metadata(file).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        Watcher { files: files.into_iter().map(|file| { let stamp = stamp(&file); (file, stamp) }).collect() }
+    }
+
+    pub fn files(&self) -> impl Iterator<Item=&Path> {
+        self.files.iter().map(|(file, _)| file.as_path())
+    }
+
+    /// The files that changed since the watcher was created or last asked. Files that
+    /// were created or deleted count as changed.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (file, last) in &mut self.files {
+            let current = stamp(file);
+            if current != *last {
+                *last = current;
+                changed.push(file.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// The outcome with its time and how the answer differs from that of the same part and
+/// input in the previous run. Answers over several lines are followed by the lines that
+/// changed, marked `-` before and `+` after.
+pub fn describe(outcome: &Outcome, previous: &[Outcome]) -> String {
+    let label = format!("Day {:02} Part {} ({})", outcome.day, outcome.part.number(), outcome.input);
+    let answer = answer(outcome);
+    let before = previous.iter()
+        .find(|before| before.day == outcome.day && before.part == outcome.part && before.input == outcome.input)
+        .map(self::answer);
+    let change = match &before {
+        None => String::new(),
+        Some(before) if *before == answer => "  unchanged".to_string(),
+        Some(before) if !before.contains('\n') && !answer.contains('\n') => format!("  was {}", before),
+        Some(_) => "  changed".to_string(),
+    };
+
+    if !answer.contains('\n') {
+        return format!("{}: {} ({:.2?}){}", label, answer, outcome.elapsed, change);
+    }
+    let mut text = format!("{}: ({:.2?}){}\n{}", label, outcome.elapsed, change, answer);
+    if let Some(before) = before.filter(|before| *before != answer) {
+        text.push_str("\nDifference:");
+        let (old, new): (Vec<&str>, Vec<&str>) = (before.lines().collect(), answer.lines().collect());
+        for line in 0..old.len().max(new.len()) {
+            match (old.get(line), new.get(line)) {
+                (Some(old), Some(new)) if old == new => {}
+                (old, new) => {
+                    if let Some(old) = old {
+                        text.push_str(&format!("\n- {}", old));
+                    }
+                    if let Some(new) = new {
+                        text.push_str(&format!("\n+ {}", new));
+                    }
+                }
+            }
+        }
+    }
+    text
+}
+
+fn answer(outcome: &Outcome) -> String {
+    match &outcome.result {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("error: {}", error),
+    }
+}
@@ -0,0 +1,215 @@
+//! The client against a small server on localhost that answers like the website would.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::client::{Client, Submission, Verdict};
+use advent_of_code_2022::config::Config;
+use advent_of_code_2022::solution::Part;
+
+const SESSION: &str = "53616c7465645f5f";
+
+/// The correct answer of every part on the mock server.
+const ANSWER: i64 = 42;
+
+/// A server for the input and answer requests of the website, counting the requests.
+struct MockServer {
+    url: String,
+    requests: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                respond(stream);
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+fn respond(mut stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut cookie = String::new();
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(": ").unwrap();
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = value.to_string(),
+            "content-length" => length = value.parse().unwrap(),
+            _ => {}
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    let body = String::from_utf8(body).unwrap();
+
+    let mut words = request_line.split_whitespace();
+    let (method, path) = (words.next().unwrap(), words.next().unwrap());
+    let route: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let (status, page) = if cookie != format!("session={}", SESSION) {
+        (400, "Puzzle inputs differ by user. Please log in to get your puzzle input.".to_string())
+    } else {
+        match (method, route.as_slice()) {
+            ("GET", ["2022", "day", day, "input"]) => (200, format!("input of day {}\n", day)),
+            ("POST", ["2022", "day", _, "answer"]) => (200, verdict(&body)),
+            _ => (404, "404 Not Found".to_string()),
+        }
+    };
+    write!(stream, "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, page.len(), page).unwrap();
+}
+
+fn verdict(form: &str) -> String {
+    let answer = form.split('&')
+        .find_map(|field| field.strip_prefix("answer="))
+        .unwrap_or_default();
+    let article = match answer.parse::<i64>() {
+        Ok(ANSWER) => "That's the right answer! You are one gold star closer.".to_string(),
+        Ok(number) if number > ANSWER => "That's not the right answer; your answer is too high.".to_string(),
+        Ok(_) => "That's not the right answer; your answer is too low.".to_string(),
+        Err(_) => "That's not the right answer. Please wait one minute before trying again.".to_string(),
+    };
+    format!("<html><body><main><article><p>{}</p></article></main></body></html>", article)
+}
+
+fn directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn client(server: &MockServer, directory: &Path) -> Client {
+    Client::new(&server.url, SESSION, Duration::ZERO, directory)
+}
+
+#[test]
+fn fetch_downloads_once() {
+    let server = MockServer::start();
+    let directory = directory("fetch");
+    let client = client(&server, &directory);
+
+    let file = client.fetch(7, false).unwrap();
+    assert_eq!(file, directory.join("day07.txt"));
+    assert_eq!(fs::read_to_string(&file).unwrap(), "input of day 7\n");
+    client.fetch(7, false).unwrap();
+    assert_eq!(server.requests(), 1);
+
+    fs::write(&file, "edited").unwrap();
+    client.fetch(7, true).unwrap();
+    assert_eq!(fs::read_to_string(&file).unwrap(), "input of day 7\n");
+    assert_eq!(server.requests(), 2);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn wrong_session_is_an_error() {
+    let server = MockServer::start();
+    let directory = directory("session");
+    let client = Client::new(&server.url, "expired", Duration::ZERO, &directory);
+    let error = client.fetch(1, false).unwrap_err();
+    assert!(error.contains("session token"), "{}", error);
+    assert!(!directory.join("day01.txt").exists());
+    let _ = fs::remove_dir_all(directory);
+}
+
+#[test]
+fn submit_reads_the_verdict() {
+    let server = MockServer::start();
+    let directory = directory("verdict");
+    let client = client(&server, &directory);
+    let sent = |verdict| Submission { verdict, sent: true };
+    assert_eq!(client.submit(1, Part::One, "100").unwrap(), sent(Verdict::TOO_HIGH));
+    assert_eq!(client.submit(1, Part::One, "7").unwrap(), sent(Verdict::TOO_LOW));
+    assert_eq!(client.submit(1, Part::Two, "ABC").unwrap(), sent(Verdict::WRONG));
+    assert_eq!(client.submit(1, Part::One, "42").unwrap(), sent(Verdict::CORRECT));
+    assert_eq!(server.requests(), 4);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn submissions_are_remembered() {
+    let server = MockServer::start();
+    let directory = directory("remember");
+    let known = |verdict| Submission { verdict, sent: false };
+
+    let client = client(&server, &directory);
+    client.submit(3, Part::One, "100").unwrap();
+    client.submit(3, Part::One, "7").unwrap();
+    client.submit(3, Part::Two, "ABC").unwrap();
+    assert_eq!(server.requests(), 3);
+
+    // A new client, as in a later run, knows the verdicts too.
+    let client = self::client(&server, &directory);
+    assert_eq!(client.submit(3, Part::Two, "ABC").unwrap(), known(Verdict::WRONG));
+    assert_eq!(client.submit(3, Part::One, "150").unwrap(), known(Verdict::TOO_HIGH));
+    assert_eq!(client.submit(3, Part::One, "-3").unwrap(), known(Verdict::TOO_LOW));
+    assert_eq!(server.requests(), 3);
+
+    assert_eq!(client.submit(3, Part::One, "42").unwrap().verdict, Verdict::CORRECT);
+    assert_eq!(client.submit(3, Part::One, "42").unwrap(), known(Verdict::CORRECT));
+    assert_eq!(client.submit(3, Part::One, "43").unwrap(), known(Verdict::WRONG));
+    assert!(client.submit(4, Part::One, "100").unwrap().sent);
+    assert_eq!(server.requests(), 5);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn requests_are_rate_limited() {
+    let server = MockServer::start();
+    let directory = directory("rate");
+    let interval = Duration::from_millis(300);
+    let client = Client::new(&server.url, SESSION, interval, &directory);
+
+    let start = Instant::now();
+    client.fetch(1, false).unwrap();
+    client.fetch(2, false).unwrap();
+    client.submit(1, Part::One, "42").unwrap();
+    assert!(start.elapsed() >= interval * 2, "{:?}", start.elapsed());
+
+    // Cached inputs and known verdicts don't wait.
+    let start = Instant::now();
+    client.fetch(1, false).unwrap();
+    client.submit(1, Part::One, "42").unwrap();
+    assert!(start.elapsed() < interval, "{:?}", start.elapsed());
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn client_from_config() {
+    let directory = directory("config");
+    let config = Config::parse("[client]\nsession = abc\nurl = http://127.0.0.1:1\ninterval = 0.5").unwrap();
+    let client = Client::from_config_with_session(&config, &directory, None).unwrap();
+    assert_eq!(client.input_file(9), directory.join("day09.txt"));
+
+    let error = Client::from_config_with_session(&Config::default(), &directory, None).err().unwrap();
+    assert!(error.contains("session"), "{}", error);
+    let error = Client::from_config_with_session(&Config::default(), &directory, Some("  ")).err().unwrap();
+    assert!(error.contains("session"), "{}", error);
+    assert!(Client::from_config_with_session(&Config::default(), &directory, Some("abc")).is_ok());
+    let config = Config::parse("[client]\nsession = abc\ninterval = soon").unwrap();
+    assert!(Client::from_config_with_session(&config, &directory, None).is_err());
+}
@@ -0,0 +1,52 @@
+//! The random input generators: every generated input has to be accepted by its solver.
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::generate::{self, Random};
+use advent_of_code_2022::runner;
+use advent_of_code_2022::solution::Part;
+
+#[test]
+fn random_numbers_are_deterministic() {
+    let (mut first, mut second) = (Random::new(42), Random::new(42));
+    let numbers = (0..5).map(|_| first.next_u64()).collect::<Vec<u64>>();
+    assert_eq!(numbers, (0..5).map(|_| second.next_u64()).collect::<Vec<u64>>());
+    assert_ne!(numbers, (0..5).map(|_| Random::new(43).next_u64()).collect::<Vec<u64>>());
+}
+
+#[test]
+fn random_numbers_stay_in_range() {
+    let mut random = Random::new(1);
+    for _ in 0..1000 {
+        assert!((-3..=3).contains(&random.range(-3..=3)));
+        assert!(random.below(7) < 7);
+    }
+    let mut items = (0..10).collect::<Vec<u32>>();
+    random.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..10).collect::<Vec<u32>>());
+}
+
+#[test]
+fn inputs_depend_on_size_and_seed() {
+    assert_eq!(generate::input(9, 50, 3), generate::input(9, 50, 3));
+    assert_ne!(generate::input(9, 50, 3), generate::input(9, 50, 4));
+    assert_ne!(generate::input(9, 50, 3), generate::input(9, 60, 3));
+    assert_eq!(generate::input(26, 50, 3), None);
+}
+
+#[test]
+fn every_day_solves_generated_inputs() {
+    for day in runner::DAYS.iter() {
+        for seed in 1..=3 {
+            let input = generate::input(day.number, 30, seed)
+                .unwrap_or_else(|| panic!("Day {} has no generator", day.number));
+            let parameters = day.resolve_parameters(false, &[]);
+            for part in Part::ALL {
+                match day.solve(&input, part, &parameters) {
+                    Ok(_) | Err(Error::NoAnswer(_)) => {}
+                    Err(error) => panic!("Day {} part {} with seed {}: {}\n{}", day.number, part.number(), seed, error, input),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Points, directions, bounding boxes and lines on small hand-made cases.
+
+use advent_of_code_2022::geometry::{self, BoundingBox, Direction, Point};
+
+fn points(coordinates: &[(i32, i32)]) -> Vec<Point<i32>> {
+    coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+}
+
+#[test]
+fn directions_are_parsed_from_letters() {
+    assert_eq!(Direction::parse("L"), Ok(Direction::LEFT));
+    assert_eq!(Direction::parse("R"), Ok(Direction::RIGHT));
+    assert_eq!(Direction::parse("U"), Ok(Direction::UP));
+    assert_eq!(Direction::parse("D"), Ok(Direction::DOWN));
+    let error = Direction::parse("X").unwrap_err();
+    assert_eq!(error.to_string(), "Expected a direction 'L', 'R', 'U' or 'D': 'X'");
+    assert!(Direction::parse("l").is_err());
+}
+
+#[test]
+fn steps_go_down_the_screen() {
+    let origin = Point::<i32>::origin();
+    assert_eq!(origin.step(Direction::UP), Point::new(0, -1));
+    assert_eq!(origin.step(Direction::DOWN_LEFT), Point::new(-1, 1));
+    assert_eq!(origin.step(Direction::UP_RIGHT), Point::new(1, -1));
+
+    let sum = Direction::ALL.iter().fold(origin, |point, direction| point.step(*direction));
+    assert_eq!(sum, origin);
+    assert!(Direction::ALL.iter().all(|direction| direction.vector::<i32>().chebyshev(origin) == 1));
+    let diagonals = Direction::ALL.iter().filter(|direction| direction.vector::<i32>().manhattan(origin) == 2).count();
+    assert_eq!(diagonals, 4);
+}
+
+#[test]
+fn distances() {
+    let (a, b) = (Point::new(1, -2), Point::new(-3, 5));
+    assert_eq!(a.manhattan(b), 11);
+    assert_eq!(a.chebyshev(b), 7);
+    assert_eq!((b - a).signum(), Point::new(-1, 1));
+    assert_eq!(Point::<u32>::new(2, 7).manhattan(Point::new(5, 3)), 7);
+}
+
+#[test]
+fn bounding_box_contains_all_points() {
+    assert_eq!(BoundingBox::<i32>::of([]), None);
+    let mut bounds = BoundingBox::of(points(&[(2, 3), (-1, 5), (4, 4)])).unwrap();
+    assert_eq!((bounds.min, bounds.max), (Point::new(-1, 3), Point::new(4, 5)));
+    assert_eq!((bounds.width(), bounds.height()), (6, 3));
+    assert!(bounds.contains(Point::new(4, 3)));
+    assert!(!bounds.contains(Point::new(0, 6)));
+
+    bounds.include(Point::new(0, 6));
+    assert!(bounds.contains(Point::new(0, 6)));
+    assert_eq!(bounds.height(), 4);
+}
+
+#[test]
+fn lines_include_both_ends() {
+    let line = |from: (i32, i32), to: (i32, i32)| geometry::line(Point::new(from.0, from.1), Point::new(to.0, to.1)).collect::<Vec<_>>();
+    assert_eq!(line((3, 1), (0, 1)), points(&[(3, 1), (2, 1), (1, 1), (0, 1)]));
+    assert_eq!(line((2, 0), (2, 2)), points(&[(2, 0), (2, 1), (2, 2)]));
+    assert_eq!(line((0, 3), (3, 0)), points(&[(0, 3), (1, 2), (2, 1), (3, 0)]));
+    assert_eq!(line((0, 0), (4, 2)), points(&[(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]));
+    assert_eq!(line((5, 5), (5, 5)), points(&[(5, 5)]));
+}
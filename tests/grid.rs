@@ -0,0 +1,97 @@
+//! The grid on small hand-made maps.
+
+use advent_of_code_2022::geometry::{Direction, Point};
+use advent_of_code_2022::grid::Grid;
+
+/// A 4x3 map of digits:
+///
+/// ```text
+/// 0123
+/// 4567
+/// 89ab
+/// ```
+fn digits() -> Grid<u32> {
+    Grid::parse("0123\n4567\n89ab\n", |c| c.to_digit(16).ok_or_else(|| "Expected a hex digit".to_string())).unwrap()
+}
+
+fn at(x: usize, y: usize) -> Point<usize> {
+    Point::new(x, y)
+}
+
+/// The positions as `(x, y)` sorted by column first.
+fn sorted(positions: impl Iterator<Item=Point<usize>>) -> Vec<(usize, usize)> {
+    let mut positions: Vec<(usize, usize)> = positions.map(|position| (position.x, position.y)).collect();
+    positions.sort();
+    positions
+}
+
+#[test]
+fn parse_reads_rows_of_cells() {
+    let grid = digits();
+    assert_eq!((grid.width(), grid.height()), (4, 3));
+    assert_eq!(grid[at(3, 0)], 3);
+    assert_eq!(grid[at(1, 2)], 9);
+    assert_eq!(grid.get(at(4, 0)), None);
+    assert_eq!(grid.position(|cell| *cell == 6), Some(at(2, 1)));
+}
+
+#[test]
+fn parse_rejects_ragged_rows_and_unknown_cells() {
+    let input = "012\n3456\n789\n";
+    let error = Grid::parse(input, |c| c.to_digit(10).ok_or_else(|| "Expected a digit".to_string())).unwrap_err();
+    assert!(error.clone().locate(input).to_string().starts_with("Expected a row of 3 cells at line 2, column 1"), "{}", error);
+
+    let input = "01\n2x\n";
+    let error = Grid::parse(input, |c| c.to_digit(10).ok_or_else(|| "Expected a digit".to_string())).unwrap_err();
+    assert!(error.locate(input).to_string().starts_with("Expected a digit at line 2, column 2"));
+
+    assert!(Grid::parse("", Ok::<char, String>).is_err());
+}
+
+#[test]
+fn rows_and_columns_up_to_the_edges() {
+    let grid = digits();
+    assert_eq!(grid.row(0).copied().collect::<Vec<u32>>(), vec![0, 1, 2, 3]);
+    assert_eq!(grid.row(2).copied().collect::<Vec<u32>>(), vec![8, 9, 10, 11]);
+    assert_eq!(grid.column(0).copied().collect::<Vec<u32>>(), vec![0, 4, 8]);
+    assert_eq!(grid.column(3).copied().collect::<Vec<u32>>(), vec![3, 7, 11]);
+}
+
+#[test]
+#[should_panic(expected = "Column 4 is outside of the 4x3 grid")]
+fn column_outside_of_the_grid_panics() {
+    let _ = digits().column(4);
+}
+
+#[test]
+#[should_panic(expected = "Row 3 is outside of the 4x3 grid")]
+fn row_outside_of_the_grid_panics() {
+    let _ = digits().row(3);
+}
+
+#[test]
+fn neighbours_stay_inside_of_the_grid() {
+    let grid = digits();
+    assert_eq!(sorted(grid.neighbours(at(0, 0))), vec![(0, 1), (1, 0)]);
+    assert_eq!(sorted(grid.neighbours(at(3, 2))), vec![(2, 2), (3, 1)]);
+    assert_eq!(sorted(grid.neighbours(at(1, 1))), vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+
+    assert_eq!(sorted(grid.neighbours_all_around(at(0, 0))), vec![(0, 1), (1, 0), (1, 1)]);
+    assert_eq!(sorted(grid.neighbours_all_around(at(3, 0))), vec![(2, 0), (2, 1), (3, 1)]);
+    assert_eq!(grid.neighbours_all_around(at(1, 1)).count(), 8);
+    assert_eq!(grid.ray(at(1, 1), Direction::RIGHT).collect::<Vec<Point<usize>>>(), vec![at(2, 1), at(3, 1)]);
+    assert_eq!(grid.step(at(0, 1), Direction::UP_LEFT), None);
+    assert_eq!(grid.step(at(0, 1), Direction::DOWN_RIGHT), Some(at(1, 2)));
+}
+
+#[test]
+#[should_panic(expected = "(4, 2) is outside of the 4x3 grid")]
+fn index_outside_of_the_grid_panics() {
+    let _ = digits()[at(4, 2)];
+}
+
+#[test]
+fn map_and_render_keep_the_layout() {
+    let grid = digits().map(|cell| cell % 2 == 0);
+    assert_eq!(grid.render(|even| if *even { '#' } else { '.' }), "#.#.\n#.#.\n#.#.\n");
+}
@@ -0,0 +1,76 @@
+//! The interval set on small hand-made sets.
+
+use std::ops::RangeInclusive;
+
+use advent_of_code_2022::interval::IntervalSet;
+
+fn set(ranges: &[RangeInclusive<i32>]) -> IntervalSet<i32> {
+    ranges.iter().cloned().collect()
+}
+
+fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+    set.iter().collect()
+}
+
+#[test]
+fn insert_merges_overlapping_and_adjacent_ranges() {
+    let set = set(&[5..=8, 1..=3, 4..=4, 12..=15, 14..=20, RangeInclusive::new(10, 9)]);
+    assert_eq!(ranges(&set), vec![1..=8, 12..=20]);
+    assert_eq!(set.covered(), 17);
+}
+
+#[test]
+fn insert_at_the_limits_does_not_overflow() {
+    let set = set(&[i32::MAX - 1..=i32::MAX, i32::MIN..=0, 1..=1]);
+    assert_eq!(ranges(&set), vec![i32::MIN..=1, i32::MAX - 1..=i32::MAX]);
+}
+
+#[test]
+fn covered_at_the_limits_does_not_overflow() {
+    assert_eq!(set(&[i32::MIN..=i32::MAX]).covered(), 1 << 32);
+    let mut set = IntervalSet::new();
+    set.insert(i64::MIN..=i64::MAX);
+    assert_eq!(set.covered(), u64::MAX);
+    set.remove(0..=0);
+    assert_eq!(set.covered(), u64::MAX);
+    set.remove(1..=1);
+    assert_eq!(set.covered(), u64::MAX - 1);
+    assert_eq!([0..=u64::MAX - 1].into_iter().collect::<IntervalSet<u64>>().covered(), u64::MAX);
+}
+
+#[test]
+fn remove_splits_ranges() {
+    let mut set = set(&[1..=10]);
+    set.remove(4..=6);
+    set.remove(10..=12);
+    assert_eq!(ranges(&set), vec![1..=3, 7..=9]);
+}
+
+#[test]
+fn set_operations() {
+    let (a, b) = (set(&[1..=5, 10..=15]), set(&[4..=11]));
+    assert_eq!(ranges(&a.union(&b)), vec![1..=15]);
+    assert_eq!(ranges(&a.intersection(&b)), vec![4..=5, 10..=11]);
+    assert_eq!(ranges(&a.difference(&b)), vec![1..=3, 12..=15]);
+    assert!(a.overlaps(&b));
+    assert!(!a.overlaps(&set(&[6..=9])));
+}
+
+#[test]
+fn containment() {
+    let set = set(&[1..=5, 10..=15]);
+    assert!(set.contains(5));
+    assert!(!set.contains(6));
+    assert!(set.contains_range(11..=15));
+    assert!(!set.contains_range(4..=10));
+    assert!(set.is_superset(&self::set(&[2..=3, 12..=12])));
+    assert!(!set.is_superset(&self::set(&[2..=7])));
+}
+
+#[test]
+fn gaps_within_bounds() {
+    let set = set(&[1..=5, 10..=15]);
+    assert_eq!(set.gaps(0..=20), vec![0..=0, 6..=9, 16..=20]);
+    assert_eq!(set.gaps(2..=12), vec![6..=9]);
+    assert!(set.gaps(2..=4).is_empty());
+}
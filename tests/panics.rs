@@ -0,0 +1,50 @@
+//! Panics of solvers are reported with their outcome, not printed by the panic hook. In a
+//! file of its own, as the panic hook is shared by all tests of a file.
+
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::parameters::Parameters;
+use advent_of_code_2022::runner::{self, Day};
+use advent_of_code_2022::solution::{Answer, Part, Solution};
+
+static PRINTED: AtomicUsize = AtomicUsize::new(0);
+
+struct Panicking;
+
+impl Solution for Panicking {
+    type Input = ();
+
+    fn parse(_: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn part1(_: &(), _: &Parameters) -> Result<Answer, Error> {
+        panic!("part 1 is broken")
+    }
+
+    fn part2(_: &(), _: &Parameters) -> Result<Answer, Error> {
+        panic!("part 2 is broken")
+    }
+}
+
+#[test]
+fn only_panics_outside_of_solvers_reach_the_hook() {
+    panic::set_hook(Box::new(|_| {
+        PRINTED.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    let day = Day::new::<Panicking>(26);
+    let result = runner::solve_guarded(&day, &"input\n".into(), Part::One, &Parameters::default(), Some(Duration::from_secs(10)));
+    match result {
+        Err(Error::Panicked(message)) => assert_eq!(message, "part 1 is broken"),
+        result => panic!("Expected a panic, got {:?}", result),
+    }
+    assert_eq!(PRINTED.load(Ordering::SeqCst), 0);
+
+    assert!(thread::spawn(|| panic!("not a solver")).join().is_err());
+    assert_eq!(PRINTED.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,61 @@
+//! Parameters out of range are reported with their name instead of breaking the solver.
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::parameters::{self, Override};
+use advent_of_code_2022::runner;
+use advent_of_code_2022::solution::{Answer, Part};
+
+const DAY14_EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+fn solve(day: u8, input: &str, part: Part, name: &str, value: i64) -> Result<Answer, Error> {
+    let day = runner::find_day(day).unwrap();
+    let overrides = [Override { day: None, name: name.to_string(), value }];
+    day.solve(input, part, &day.resolve_parameters(true, &overrides))
+}
+
+fn invalid(result: Result<Answer, Error>) -> String {
+    match result {
+        Err(Error::Parameter(message)) => message,
+        result => panic!("Expected an invalid parameter, got {:?}", result),
+    }
+}
+
+#[test]
+fn markers_have_characters() {
+    assert_eq!(invalid(solve(6, "abcd\n", Part::One, "packet_marker", 0)), "packet_marker = 0: a marker needs at least one character");
+    assert_eq!(invalid(solve(6, "abcd\n", Part::Two, "message_marker", -3)), "message_marker = -3: a marker needs at least one character");
+    assert_eq!(solve(6, "abcd\n", Part::One, "packet_marker", 1).unwrap(), Answer::Number(1));
+}
+
+#[test]
+fn ropes_have_knots_and_monkeys_relief() {
+    assert_eq!(invalid(solve(9, "R 4\n", Part::One, "short_rope", 0)), "short_rope = 0: a rope needs at least one knot");
+    let monkey = "Monkey 0:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 2\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n";
+    assert_eq!(invalid(solve(11, monkey, Part::One, "part1_relief", 0)), "part1_relief = 0: the worry level cannot be divided by zero");
+}
+
+#[test]
+fn caves_hold_the_rocks_and_the_sand() {
+    assert_eq!(invalid(solve(14, DAY14_EXAMPLE, Part::One, "part1_cave_size", 0)),
+               "part1_cave_size = 0: the rocks and the sand need a cave of at least 505x505");
+    assert_eq!(solve(14, DAY14_EXAMPLE, Part::One, "part1_cave_size", 505).unwrap(), Answer::Number(24));
+    assert_eq!(invalid(solve(14, DAY14_EXAMPLE, Part::Two, "part2_cave_size", 512)),
+               "part2_cave_size = 512: the rocks and the sand need a cave of at least 513x513");
+    assert_eq!(solve(14, DAY14_EXAMPLE, Part::Two, "part2_cave_size", 513).unwrap(), Answer::Number(93));
+}
+
+#[test]
+fn distress_beacon_is_searched_from_the_origin() {
+    let sensor = "Sensor at x=2, y=2: closest beacon is at x=3, y=2\n";
+    assert_eq!(invalid(solve(15, sensor, Part::Two, "search_max", -1)),
+               "search_max = -1: the distress beacon is searched from x = 0 and y = 0 on");
+}
+
+#[test]
+fn days_are_those_of_advent_of_code() {
+    assert_eq!(parameters::parse_day("day15"), Ok(15));
+    assert_eq!(parameters::parse_day("25"), Ok(25));
+    assert_eq!(parameters::parse_day("0"), Err("There is no day 0 in Advent of Code".to_string()));
+    assert_eq!(parameters::parse_day("26"), Err("There is no day 26 in Advent of Code".to_string()));
+    assert_eq!(parameters::parse_day("x"), Err("'x' is not a valid day".to_string()));
+}
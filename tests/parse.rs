@@ -0,0 +1,132 @@
+//! The parsing helpers, and the checks of the days, on small hand-made snippets.
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::parse::{self, Pattern};
+use advent_of_code_2022::runner;
+use advent_of_code_2022::solution::{Answer, Part};
+
+fn solve(day: u8, input: &str) -> Result<Answer, Error> {
+    let day = runner::find_day(day).unwrap();
+    day.solve(input, Part::One, &day.resolve_parameters(false, &[]))
+}
+
+#[test]
+fn blocks_are_separated_by_blank_lines() {
+    let blocks = parse::blocks("\n1\n2\n\n3\n\n\n4\r\n\r\n5").collect::<Vec<&str>>();
+    assert_eq!(blocks, vec!["1\n2", "3", "4", "5"]);
+}
+
+#[test]
+fn blocks_of_empty_input() {
+    assert_eq!(parse::blocks("\n\n").count(), 0);
+}
+
+#[test]
+fn integers_keep_their_sign() {
+    let integers = parse::integers::<i32>("x=-12, y=7 at 2-4 -").unwrap();
+    assert_eq!(integers, vec![-12, 7, 2, 4]);
+}
+
+#[test]
+fn integers_that_are_too_large_are_errors() {
+    let error = parse::integers::<u8>("1 256").unwrap_err();
+    assert_eq!(error.to_string(), "Expected a number: '256'");
+}
+
+#[test]
+fn exact_integers_count_the_integers() {
+    assert_eq!(parse::exact_integers::<i32, 2>("x=3, y=-4", "x=<x>, y=<y>"), Ok([3, -4]));
+    let error = parse::exact_integers::<i32, 2>("x=3", "x=<x>, y=<y>").unwrap_err();
+    assert_eq!(error.to_string(), "Expected 'x=<x>, y=<y>': 'x=3'");
+}
+
+#[test]
+fn records_have_typed_fields() {
+    let pattern = Pattern::new(r"(?P<name>\w+): (?P<values>[\d, ]*)(?P<rest> more)?", "<name>: <values>");
+    let record = pattern.matches("items: 1, 2, 3").unwrap();
+    assert_eq!(record.str("name"), "items");
+    assert_eq!(record.numbers::<u32>("values", ","), Ok(vec![1, 2, 3]));
+    assert_eq!(record.str("rest"), "");
+}
+
+#[test]
+fn records_must_match_the_whole_snippet() {
+    let pattern = Pattern::new(r"move (?P<count>\d+)", "move <count>");
+    let error = pattern.matches("move 3 now").unwrap_err();
+    assert_eq!(error.to_string(), "Expected 'move <count>': 'move 3 now'");
+}
+
+#[test]
+fn errors_in_fields_point_at_the_field() {
+    let input = "size: 99999999999";
+    let pattern = Pattern::new(r"size: (?P<size>\d+)", "size: <size>");
+    let error = pattern.matches(input).unwrap().number::<u32>("size").unwrap_err().locate(input);
+    assert!(error.to_string().starts_with("Expected a number at line 1, column 7"), "{}", error);
+}
+
+#[test]
+fn moves_need_enough_crates_on_the_stack() {
+    let stacks = "[A]\n[B] [C]\n 1   2\n\n";
+    assert_eq!(solve(5, &format!("{}move 2 from 1 to 2\nmove 3 from 2 to 1\n", stacks)).unwrap(), Answer::from("C "));
+    let error = solve(5, &format!("{}move 2 from 1 to 2\nmove 4 from 2 to 1\n", stacks)).unwrap_err();
+    assert_eq!(error.to_string(), "\
+Expected at most 3 crates, stack 2 holds no more at line 6, column 6
+  |
+6 | move 4 from 2 to 1
+  |      ^");
+}
+
+fn monkey(id: u64, divisor: u64, target: u64) -> String {
+    format!("\
+Monkey {}:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey 0
+", id, divisor, target)
+}
+
+#[test]
+fn monkeys_throw_to_existing_monkeys() {
+    assert!(solve(11, &format!("{}\n{}", monkey(0, 23, 1), monkey(1, 23, 0))).is_ok());
+    let error = solve(11, &format!("{}\n{}", monkey(0, 23, 1), monkey(1, 23, 12))).unwrap_err();
+    assert_eq!(error.to_string(), "\
+Expected a monkey to throw to, there is no monkey 12 at line 12, column 30
+   |
+12 |     If true: throw to monkey 12
+   |                              ^^");
+}
+
+#[test]
+fn monkeys_have_different_ids() {
+    let error = solve(11, &format!("{}\n{}", monkey(0, 23, 0), monkey(0, 23, 0))).unwrap_err();
+    assert_eq!(error.to_string(), "\
+Expected a new monkey, there is a monkey 0 already at line 8, column 8
+  |
+8 | Monkey 0:
+  |        ^");
+}
+
+#[test]
+fn monkeys_do_not_divide_by_zero() {
+    let error = solve(11, &monkey(0, 0, 0)).unwrap_err();
+    assert_eq!(error.to_string(), "\
+Expected a divisor other than 0 at line 4, column 22
+  |
+4 |   Test: divisible by 0
+  |                      ^");
+}
+
+#[test]
+fn the_divisors_of_the_monkeys_fit_a_u64() {
+    let divisor = u64::MAX / 2;
+    let error = solve(11, &format!("{}\n{}", monkey(0, divisor, 1), monkey(1, 3, 0))).unwrap_err();
+    assert!(matches!(error, Error::NoAnswer(_)), "{}", error);
+}
+
+#[test]
+fn packets_of_a_pair_differ() {
+    let error = solve(13, "[1,2]\n[1,3]\n\n[[4]]\n[4]\n").unwrap_err();
+    assert_eq!(error.to_string(), "No answer: The packets of pair 2 are equal, so they have no order");
+}
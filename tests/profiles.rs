@@ -0,0 +1,74 @@
+//! Puzzle inputs of profiles and the table comparing their answers.
+
+use std::time::Duration;
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::files;
+use advent_of_code_2022::parse::ParseError;
+use advent_of_code_2022::report;
+use advent_of_code_2022::runner::{self, InputSource, Outcome};
+use advent_of_code_2022::solution::{Answer, Part};
+
+fn outcome(day: u8, part: Part, result: Result<Answer, Error>) -> Outcome {
+    Outcome { day, part, input: String::new(), elapsed: Duration::ZERO, result }
+}
+
+#[test]
+fn profile_inputs_are_in_their_own_directory() {
+    let day = runner::find_day(4).unwrap();
+    let source = InputSource::Profile("alice".to_string());
+    assert_eq!(source.describe(day), "resources/alice/day04.txt");
+    assert!(!source.is_explicit());
+    assert!(!source.is_example());
+    assert!(matches!(source.read(day), Err(files::Error::NotFound(_))));
+}
+
+#[test]
+fn table_aligns_columns() {
+    let inputs = ["default".to_string(), "alice".to_string()];
+    let columns = [
+        vec![outcome(1, Part::One, Ok(Answer::Number(70369))), outcome(1, Part::Two, Ok(Answer::Number(203002)))],
+        vec![outcome(1, Part::One, Ok(Answer::Number(12))), outcome(1, Part::Two, Err(Error::no_answer("no elves")))],
+    ];
+    assert_eq!(report::table(&inputs, &columns), concat!(
+        "               default  alice\n",
+        "Day 01 Part 1  70369    12\n",
+        "Day 01 Part 2  203002   error: No answer: no elves\n",
+    ));
+}
+
+#[test]
+fn table_spreads_text_over_lines() {
+    let inputs = ["default".to_string(), "bob".to_string()];
+    let missing = Err(Error::Input(files::Error::NotFound("resources/bob/day10.txt".to_string())));
+    let columns = [
+        vec![outcome(10, Part::Two, Ok(Answer::Text("#..\n.#.\n..#".to_string())))],
+        vec![outcome(10, Part::Two, missing)],
+    ];
+    assert_eq!(report::table(&inputs, &columns), concat!(
+        "               default  bob\n",
+        "Day 10 Part 2  #..      -\n",
+        "               .#.\n",
+        "               ..#\n",
+    ));
+}
+
+#[test]
+fn table_follows_errors_over_several_lines() {
+    let inputs = ["default".to_string(), "carol".to_string()];
+    let input = "1000\nx\n";
+    let error = Error::from(ParseError::new(&input[5..6], "Expected a number").locate(input));
+    let columns = [
+        vec![outcome(1, Part::One, Ok(Answer::Number(1000)))],
+        vec![outcome(1, Part::One, Err(error))],
+    ];
+    assert_eq!(report::table(&inputs, &columns), concat!(
+        "               default  carol\n",
+        "Day 01 Part 1  1000     error: Expected a number at line 2, column 1\n",
+        "\n",
+        "Day 01 Part 1 (carol): error: Expected a number at line 2, column 1\n",
+        "  |\n",
+        "2 | x\n",
+        "  | ^\n",
+    ));
+}
@@ -0,0 +1,71 @@
+//! Property tests comparing the fast solvers with slow but obviously correct versions on
+//! random inputs. Failing inputs are shrunk to a minimal example before being reported.
+
+use proptest::prelude::*;
+
+use advent_of_code_2022::day08::{self, Forest};
+use advent_of_code_2022::day12::Map;
+use advent_of_code_2022::day15::{self, Deployment};
+use advent_of_code_2022::grid::Grid;
+
+/// Forests of up to 12 by 12 trees.
+fn forest() -> impl Strategy<Value=Forest> {
+    (1..=12usize, 1..=12usize).prop_flat_map(|(width, height)| {
+        prop::collection::vec(0..=9u32, width * height)
+            .prop_map(move |heights| Grid::new(width, height, heights))
+    })
+}
+
+/// Maps of up to 8 by 8 squares of elevation 'a' to 'e' with the end somewhere on them.
+fn map() -> impl Strategy<Value=String> {
+    (2..=8usize, 2..=8usize).prop_flat_map(|(width, height)| {
+        (prop::collection::vec(b'a'..=b'e', width * height), 0..width * height).prop_map(move |(mut squares, end)| {
+            squares[end] = b'E';
+            squares.chunks(width).map(|row| String::from_utf8(row.to_vec()).unwrap()).collect::<Vec<String>>().join("\n")
+        })
+    })
+}
+
+/// Up to 8 sensors close to the origin.
+fn deployments() -> impl Strategy<Value=Vec<Deployment>> {
+    prop::collection::vec((-30..=30i32, -30..=30i32, -30..=30i32, -30..=30i32), 0..=8).prop_map(|sensors| {
+        sensors.into_iter()
+            .map(|(x, y, beacon_x, beacon_y)| {
+                format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}", x, y, beacon_x, beacon_y)
+                    .parse::<Deployment>()
+                    .unwrap()
+            })
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn day08_visible_trees(forest in forest()) {
+        let visible = day08::visible_trees(&forest);
+        for tree in forest.positions() {
+            prop_assert_eq!(visible[tree], day08::can_view_border(&forest, tree), "tree {:?}", tree);
+        }
+    }
+
+    #[test]
+    fn day08_scenic_scores(forest in forest()) {
+        let scores = day08::scenic_scores(&forest);
+        for tree in forest.positions() {
+            prop_assert_eq!(scores[tree], day08::scenic_score_of(&forest, tree), "tree {:?}", tree);
+        }
+    }
+
+    #[test]
+    fn day12_fewest_steps_from_any_start(squares in map()) {
+        let map = Map::from(&squares).unwrap();
+        let end = map.find('E').unwrap();
+        prop_assert_eq!(map.fewest_steps(map.find_all('a'), end), map.fewest_steps_naive(map.find_all('a'), end));
+    }
+
+    #[test]
+    fn day15_positions_without_beacon(deployments in deployments(), row in -40..=40i32) {
+        let fast = day15::positions_without_beacon(&deployments, row);
+        prop_assert_eq!(fast as usize, day15::positions_without_beacon_naive(&deployments, row));
+    }
+}
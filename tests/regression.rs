@@ -0,0 +1,115 @@
+//! Regression tests that solve every puzzle input in `resources/` and compare the
+//! answers with the manifest `resources/answers.txt`.
+
+use std::fs;
+
+use advent_of_code_2022::files;
+use advent_of_code_2022::runner;
+use advent_of_code_2022::solution::Part;
+
+const MANIFEST: &str = "resources/answers.txt";
+
+struct Expected {
+    file: String,
+    part: u8,
+    answer: String,
+}
+
+fn manifest() -> Vec<Expected> {
+    let manifest = fs::read_to_string(MANIFEST).expect("Could not read the answer manifest");
+    manifest.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (file, rest) = line.split_once(char::is_whitespace).unwrap_or_else(|| invalid(line));
+            let (part, answer) = rest.trim_start().split_once(char::is_whitespace).unwrap_or_else(|| invalid(line));
+            Expected {
+                file: file.to_string(),
+                part: part.parse().unwrap_or_else(|_| invalid(line)),
+                answer: answer.trim().replace("\\n", "\n"),
+            }
+        })
+        .collect()
+}
+
+fn invalid<T>(line: &str) -> T {
+    panic!("Invalid manifest line '{}'", line)
+}
+
+fn check(file: &str, part: Part) {
+    let expected = manifest().into_iter()
+        .find(|expected| expected.file == file && expected.part == part.number())
+        .unwrap_or_else(|| panic!("{} has no answer for part {} in {}", file, part.number(), MANIFEST))
+        .answer;
+    let number = file[3..5].parse().expect("Input files are named dayNN[-test].txt");
+    let day = runner::find_day(number).unwrap_or_else(|| panic!("Day {} is not solved yet", number));
+    let input = files::parse_string_from(format!("resources/{}", file)).unwrap();
+    let parameters = day.resolve_parameters(file.ends_with("-test.txt"), &[]);
+    match day.solve(&input, part, &parameters) {
+        Ok(answer) => assert_eq!(answer.to_string(), expected, "{} part {}", file, part.number()),
+        Err(error) => panic!("{} part {} failed: {}", file, part.number(), error),
+    }
+}
+
+macro_rules! regression_tests {
+    ($($(#[$attribute:meta])* $name:ident: $file:literal,)*) => {
+        $(
+            mod $name {
+                use advent_of_code_2022::solution::Part;
+
+                #[test]
+                $(#[$attribute])*
+                fn part1() {
+                    super::check($file, Part::One);
+                }
+
+                #[test]
+                $(#[$attribute])*
+                fn part2() {
+                    super::check($file, Part::Two);
+                }
+            }
+        )*
+    };
+}
+
+regression_tests! {
+    day01: "day01.txt",
+    day02: "day02.txt",
+    day03: "day03.txt",
+    day04: "day04.txt",
+    day05: "day05.txt",
+    day06: "day06.txt",
+    day07_example: "day07-test.txt",
+    day07: "day07.txt",
+    day08_example: "day08-test.txt",
+    day08: "day08.txt",
+    day09_example: "day09-test.txt",
+    day09: "day09.txt",
+    day10_example: "day10-test.txt",
+    day10: "day10.txt",
+    day11_example: "day11-test.txt",
+    day11: "day11.txt",
+    day12_example: "day12-test.txt",
+    day12: "day12.txt",
+    day13_example: "day13-test.txt",
+    day13: "day13.txt",
+    day14_example: "day14-test.txt",
+    day14: "day14.txt",
+    day15_example: "day15-test.txt",
+    day15: "day15.txt",
+}
+
+#[test]
+fn every_input_has_answers() {
+    let manifest = manifest();
+    for entry in fs::read_dir("resources").unwrap() {
+        let file = entry.unwrap().file_name().to_string_lossy().to_string();
+        if !file.starts_with("day") {
+            continue;
+        }
+        for part in Part::ALL {
+            assert!(manifest.iter().any(|expected| expected.file == file && expected.part == part.number()),
+                    "{} has no answer for part {} in {}", file, part.number(), MANIFEST);
+        }
+    }
+}
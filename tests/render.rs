@@ -0,0 +1,113 @@
+//! Pictures of small hand-made grids and of the grids of some days.
+
+use std::fs;
+
+use advent_of_code_2022::day08;
+use advent_of_code_2022::day14::{self, Day14};
+use advent_of_code_2022::geometry::Point;
+use advent_of_code_2022::grid::Grid;
+use advent_of_code_2022::render::{self, Colour, Frames, ImageFormat};
+use advent_of_code_2022::runner;
+use advent_of_code_2022::simulation::{Driver, Simulation};
+use advent_of_code_2022::solution::{Part, Solution};
+
+fn picture() -> Grid<Colour> {
+    Grid::new(2, 1, vec![Colour::RED, Colour::WHITE])
+}
+
+#[test]
+fn ansi_colours_changes_only() {
+    let grid = Grid::new(3, 1, vec![1, 1, 2]);
+    let text = render::ansi(&grid, |value| if *value == 1 { ('#', Colour::RED) } else { ('.', Colour::BLACK) });
+    assert_eq!(text, "\x1b[38;2;220;50;47m##\x1b[38;2;0;0;0m.\x1b[0m\n");
+}
+
+#[test]
+fn ppm_scales_cells() {
+    let image = render::ppm(&picture(), 2);
+    let header = b"P6\n4 2\n255\n";
+    assert_eq!(&image[..header.len()], header);
+    let row = [220, 50, 47, 220, 50, 47, 255, 255, 255, 255, 255, 255];
+    assert_eq!(&image[header.len()..], [row, row].concat());
+}
+
+#[test]
+fn png_has_valid_chunks() {
+    let image = render::png(&picture(), 1);
+    assert_eq!(&image[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&image[12..16], b"IHDR");
+    assert_eq!(&image[16..24], [0, 0, 0, 2, 0, 0, 0, 1]);
+    // The end chunk is always the same, including its checksum.
+    assert_eq!(&image[image.len() - 12..], [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+}
+
+#[test]
+fn blend_between_colours() {
+    assert_eq!(Colour::BLACK.blend(Colour::WHITE, 0.5), Colour::rgb(128, 128, 128));
+    assert_eq!(Colour::BLACK.blend(Colour::WHITE, 2.0), Colour::WHITE);
+}
+
+#[test]
+fn frames_are_numbered_files() {
+    let directory = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+    let mut frames = Frames::create(&directory, ImageFormat::PPM, 1).unwrap();
+    let first = frames.write(&picture()).unwrap();
+    let second = frames.write(&picture()).unwrap();
+    assert_eq!(first, directory.join("frame00000.ppm"));
+    assert_eq!(second, directory.join("frame00001.ppm"));
+    assert_eq!(fs::read(&second).unwrap(), render::ppm(&picture(), 1));
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn forest_heights_are_shades() {
+    let forest = day08::parse_forest("09\n90").unwrap();
+    let picture = day08::picture(&forest);
+    assert_eq!(picture[Point::new(0, 0)], Colour::BROWN);
+    assert_eq!(picture[Point::new(1, 0)], Colour::GREEN);
+}
+
+#[test]
+fn cave_picture_covers_rocks_and_sand() {
+    let input = fs::read_to_string("resources/day14-test.txt").unwrap();
+    let paths = Day14::parse(&input).unwrap();
+    let mut driver = Driver::new(day14::construct_cave(&paths, 1000, 1000));
+    driver.run();
+    let cave = driver.simulation();
+    let picture = cave.picture();
+    assert_eq!((picture.width(), picture.height()), (10, 10));
+    let text = cave.render();
+    assert_eq!(picture.iter().filter(|(_, colour)| **colour == Colour::YELLOW).count(), text.matches('o').count());
+}
+
+#[test]
+fn image_formats_by_name() {
+    assert_eq!(ImageFormat::parse("png"), Some(ImageFormat::PNG));
+    assert_eq!(ImageFormat::parse("ppm").map(ImageFormat::extension), Some("ppm"));
+    assert_eq!(ImageFormat::parse("gif"), None);
+}
+
+#[test]
+fn rope_frames_follow_every_step() {
+    let day = runner::find_day(9).unwrap();
+    let pictures = day.pictures("R 4\nU 4\n", Part::One, &day.resolve_parameters(true, &[])).unwrap();
+    assert_eq!(pictures.len(), 9);
+    assert_eq!((pictures[0].width(), pictures[0].height()), (1, 1));
+    assert_eq!((pictures[8].width(), pictures[8].height()), (5, 5));
+}
+
+#[test]
+fn cave_frames_leave_out_the_floor() {
+    let input = fs::read_to_string("resources/day14-test.txt").unwrap();
+    let day = runner::find_day(14).unwrap();
+    let pictures = day.pictures(&input, Part::Two, &day.resolve_parameters(true, &[])).unwrap();
+    assert_eq!(pictures.len(), 94);
+    assert_eq!((pictures[0].width(), pictures[0].height()), (10, 12));
+    assert_eq!((pictures[93].width(), pictures[93].height()), (21, 12));
+}
+
+#[test]
+fn days_without_pictures_draw_nothing() {
+    let day = runner::find_day(1).unwrap();
+    assert!(day.pictures("1\n", Part::One, &day.resolve_parameters(true, &[])).unwrap().is_empty());
+}
@@ -0,0 +1,82 @@
+//! Solvers that panic or run too long are reported without stopping the run.
+
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::parameters::Parameters;
+use advent_of_code_2022::runner::{self, Day, InputSource};
+use advent_of_code_2022::solution::{Answer, Part, Solution};
+
+/// A sensor that sees every position up to the search limit, so part 2 checks every row.
+const NO_GAP: &str = "Sensor at x=2000000, y=2000000: closest beacon is at x=8000000, y=8000000\n";
+
+/// Counts the lines, but panics in part 1.
+struct Panicking;
+
+impl Solution for Panicking {
+    type Input = usize;
+
+    fn parse(input: &str) -> Result<usize, Error> {
+        Ok(input.lines().count())
+    }
+
+    fn part1(_: &usize, _: &Parameters) -> Result<Answer, Error> {
+        panic!("part 1 is broken")
+    }
+
+    fn part2(lines: &usize, _: &Parameters) -> Result<Answer, Error> {
+        Ok((*lines).into())
+    }
+}
+
+#[test]
+fn slow_solver_times_out() {
+    let day = runner::find_day(15).unwrap();
+    let parameters = day.resolve_parameters(false, &[]);
+    let timeout = Duration::from_millis(50);
+    let start = Instant::now();
+    let result = runner::solve_guarded(day, &NO_GAP.into(), Part::Two, &parameters, Some(timeout));
+    assert!(matches!(result, Err(Error::TimedOut(limit)) if limit == timeout), "{:?}", result);
+    assert!(start.elapsed() < Duration::from_secs(1), "{:?}", start.elapsed());
+}
+
+#[test]
+fn slow_benchmark_times_out() {
+    let day = runner::find_day(15).unwrap();
+    let parameters = day.resolve_parameters(false, &[]);
+    let start = Instant::now();
+    let result = runner::measure_guarded(day, &NO_GAP.into(), &[Part::Two], &parameters, 2, Some(Duration::from_millis(50)));
+    assert!(matches!(result, Err(Error::TimedOut(limit)) if limit == Duration::from_millis(100)), "{:?}", result);
+    assert!(start.elapsed() < Duration::from_secs(1), "{:?}", start.elapsed());
+
+    let day = runner::find_day(1).unwrap();
+    let parameters = day.resolve_parameters(false, &[]);
+    let timings = runner::measure_guarded(day, &"1\n2\n\n4\n".into(), &Part::ALL, &parameters, 3, Some(Duration::from_secs(10)));
+    assert_eq!(timings.unwrap().part2.len(), 3);
+}
+
+#[test]
+fn fast_solver_finishes_within_the_timeout() {
+    let day = runner::find_day(1).unwrap();
+    let parameters = day.resolve_parameters(false, &[]);
+    let result = runner::solve_guarded(day, &"1\n2\n\n4\n".into(), Part::One, &parameters, Some(Duration::from_secs(10)));
+    assert_eq!(result.unwrap(), Answer::Number(4));
+}
+
+#[test]
+fn run_goes_on_after_a_panic() {
+    let day = Day::new::<Panicking>(26);
+    let source = InputSource::Inline("1\n2\n3\n".to_string());
+    let outcomes = runner::run(&[&day], &Part::ALL, &source, &[], Some(Duration::from_secs(10)), |_| ());
+    match &outcomes[0].result {
+        Err(Error::Panicked(message)) => assert_eq!(message, "part 1 is broken"),
+        result => panic!("Expected a panic, got {:?}", result),
+    }
+    assert_eq!(outcomes[1].result.as_ref().unwrap(), &Answer::Number(3));
+}
+
+#[test]
+fn timeout_is_reported() {
+    assert_eq!(Error::TimedOut(Duration::from_secs(60)).to_string(), "Timed out after 60.00s");
+    assert_eq!(Error::Panicked("index out of bounds".to_string()).to_string(), "Panicked: index out of bounds");
+}
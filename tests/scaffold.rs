@@ -0,0 +1,85 @@
+//! New days created in a copy of the files the days are registered in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2022::runner;
+use advent_of_code_2022::scaffold;
+
+const REGISTRIES: [&str; 5] = ["src/lib.rs", "src/runner.rs", "src/generate.rs", "tests/regression.rs", "resources/answers.txt"];
+
+fn repository(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for file in REGISTRIES {
+        fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+        fs::copy(file, root.join(file)).unwrap();
+    }
+    root
+}
+
+fn read(root: &Path, file: &str) -> String {
+    fs::read_to_string(root.join(file)).unwrap()
+}
+
+#[test]
+fn new_day_is_created_and_registered() {
+    let root = repository("create");
+    let written = scaffold::create(&root, 25).unwrap();
+    assert_eq!(written.len(), 3 + REGISTRIES.len());
+
+    assert!(read(&root, "src/day25.rs").contains("impl Solution for Day25 {"));
+    assert_eq!(read(&root, "resources/day25.txt"), "");
+    assert_eq!(read(&root, "resources/day25-test.txt"), "");
+    assert!(read(&root, "src/lib.rs").ends_with("\npub mod day25;\n"));
+    let runner = read(&root, "src/runner.rs");
+    assert!(runner.contains(", day25};"));
+    assert!(runner.contains(&format!("pub const DAYS: [Day; {}] = [", runner::DAYS.len() + 1)));
+    assert!(runner.contains("\n    Day::new::<day25::Day25>(25),\n];"));
+    let generate = read(&root, "src/generate.rs");
+    assert!(generate.contains("\n        25 => day25,\n        _ => return None,"));
+    assert!(generate.contains("\nfn day25(random: &mut Random, lines: usize) -> String {"));
+    let regression = read(&root, "tests/regression.rs");
+    assert!(regression.contains("#[ignore = \"the answers in resources/answers.txt are placeholders\"] day25: \"day25.txt\","));
+    assert!(read(&root, "resources/answers.txt").ends_with("day25-test.txt  1 ?\nday25-test.txt  2 ?\nday25.txt       1 ?\nday25.txt       2 ?\n"));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn days_are_kept_in_order() {
+    let root = repository("order");
+    scaffold::create(&root, 24).unwrap();
+    scaffold::create(&root, 21).unwrap();
+    for (file, first, second) in [
+        ("src/lib.rs", "pub mod day21;", "pub mod day24;"),
+        ("src/runner.rs", "(21),", "(24),"),
+        ("src/generate.rs", "21 => day21,", "24 => day24,"),
+        ("src/generate.rs", "fn day21(", "fn day24("),
+        ("tests/regression.rs", "day21:", "day24_example:"),
+        ("resources/answers.txt", "day21.txt", "day24-test.txt"),
+    ] {
+        let text = read(&root, file);
+        assert!(text.find(first).unwrap() < text.find(second).unwrap(), "{} before {} in {}", first, second, file);
+    }
+    assert!(read(&root, "src/runner.rs").contains(", day21, day24};"));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn nothing_is_overwritten() {
+    let root = repository("overwrite");
+    let before = read(&root, "src/lib.rs");
+
+    let error = scaffold::create(&root, 1).unwrap_err();
+    assert!(error.contains("there already"), "{}", error);
+
+    fs::write(root.join("resources/day23-test.txt"), "mine").unwrap();
+    let error = scaffold::create(&root, 23).unwrap_err();
+    assert!(error.contains("resources/day23-test.txt exists already"), "{}", error);
+    assert_eq!(read(&root, "resources/day23-test.txt"), "mine");
+    assert!(!root.join("src/day23.rs").exists());
+    assert_eq!(read(&root, "src/lib.rs"), before);
+
+    assert!(scaffold::create(&root, 26).is_err());
+    fs::remove_dir_all(root).unwrap();
+}
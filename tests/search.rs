@@ -0,0 +1,63 @@
+//! The search algorithms on small hand-made graphs.
+
+use advent_of_code_2022::search::{self, Graph};
+
+/// Directed edges with costs between numbered nodes.
+struct Edges(Vec<(u32, u32, u64)>);
+
+impl Graph for Edges {
+    type Node = u32;
+
+    fn neighbours(&self, node: &u32) -> Vec<(u32, u64)> {
+        self.0.iter()
+            .filter(|(from, _, _)| from == node)
+            .map(|(_, to, cost)| (*to, *cost))
+            .collect()
+    }
+}
+
+fn graph() -> Edges {
+    // 0 -> 1 -> 2 -> 3 is long but cheap, 0 -> 3 is short but expensive.
+    Edges(vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 10), (4, 3, 1)])
+}
+
+#[test]
+fn bfs_finds_fewest_steps() {
+    let found = search::bfs(&graph(), [0], |node| *node == 3).unwrap();
+    assert_eq!(found.cost, 1);
+    assert_eq!(found.path(), vec![0, 3]);
+}
+
+#[test]
+fn dijkstra_finds_cheapest_path() {
+    let found = search::dijkstra(&graph(), [0], |node| *node == 3).unwrap();
+    assert_eq!(found.cost, 3);
+    assert_eq!(found.path(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn a_star_with_admissible_heuristic_finds_cheapest_path() {
+    let found = search::a_star(&graph(), [0], |node| *node == 3, |node| 3 - (*node).min(3) as u64).unwrap();
+    assert_eq!(found.cost, 3);
+    assert_eq!(found.path(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn search_starts_from_every_source() {
+    let found = search::dijkstra(&graph(), [0, 4], |node| *node == 3).unwrap();
+    assert_eq!(found.cost, 1);
+    assert_eq!(found.path(), vec![4, 3]);
+}
+
+#[test]
+fn unreachable_goal_is_none() {
+    assert!(search::bfs(&graph(), [3], |node| *node == 0).is_none());
+    assert!(search::dijkstra(&graph(), [1], |node| *node == 4).is_none());
+}
+
+#[test]
+fn start_can_be_the_goal() {
+    let found = search::bfs(&graph(), [2], |node| *node == 2).unwrap();
+    assert_eq!(found.cost, 0);
+    assert_eq!(found.path(), vec![2]);
+}
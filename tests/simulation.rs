@@ -0,0 +1,105 @@
+//! The simulation driver on a counter and on the simulations of some days.
+
+use advent_of_code_2022::day05::{Crane, Day05, Model};
+use advent_of_code_2022::day14::{self, Day14};
+use advent_of_code_2022::simulation::{Driver, Simulation};
+use advent_of_code_2022::solution::Solution;
+
+/// Counts up to a limit.
+struct Counter {
+    value: u32,
+    limit: u32,
+}
+
+impl Simulation for Counter {
+    type Snapshot = u32;
+
+    fn step(&mut self) {
+        if !self.is_done() {
+            self.value += 1;
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.value == self.limit
+    }
+
+    fn snapshot(&self) -> u32 {
+        self.value
+    }
+}
+
+fn counter(limit: u32) -> Counter {
+    Counter { value: 0, limit }
+}
+
+#[test]
+fn run_to_completion() {
+    let mut driver = Driver::new(counter(5));
+    assert_eq!(driver.run(), 5);
+    assert!(driver.simulation().is_done());
+    assert_eq!(driver.run(), 0);
+    assert_eq!(driver.steps(), 5);
+}
+
+#[test]
+fn run_some_steps() {
+    let mut driver = Driver::new(counter(5));
+    assert_eq!(driver.run_steps(3), 3);
+    assert_eq!(driver.run_steps(3), 2);
+    assert_eq!(driver.steps(), 5);
+}
+
+#[test]
+fn run_until_predicate() {
+    let mut driver = Driver::new(counter(5));
+    assert!(driver.run_until(|counter| counter.value == 2));
+    assert_eq!(driver.steps(), 2);
+    assert!(driver.run_until(|counter| counter.value == 2));
+    assert_eq!(driver.steps(), 2);
+    assert!(!driver.run_until(|counter| counter.value == 10));
+    assert_eq!(driver.into_simulation().value, 5);
+}
+
+#[test]
+fn replay_recorded_snapshots() {
+    let mut driver = Driver::recording(counter(3));
+    driver.run();
+    let replay = driver.replay().map(|(step, value)| (step, *value)).collect::<Vec<(usize, u32)>>();
+    assert_eq!(replay, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+    assert_eq!(Driver::new(counter(3)).replay().count(), 0);
+}
+
+#[test]
+fn crane_moves_one_instruction_per_step() {
+    let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n";
+    let (stacks, instructions) = Day05::parse(input).unwrap();
+    let mut driver = Driver::recording(Crane::new(Model::CRATE_MOVER_9000, stacks, &instructions));
+    driver.run_steps(1);
+    let (_, after_first) = driver.replay().last().unwrap();
+    assert_eq!(after_first.to_string(), "[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+    assert_eq!(driver.run(), 3);
+    assert_eq!(driver.steps(), 4);
+    assert_eq!(driver.simulation().stacks().stack_message(), "CMZ");
+}
+
+#[test]
+fn cave_renders_rocks_and_sand() {
+    let input = std::fs::read_to_string("resources/day14-test.txt").unwrap();
+    let paths = Day14::parse(&input).unwrap();
+    let mut driver = Driver::new(day14::construct_cave(&paths, 1000, 1000));
+    driver.run_steps(5);
+    let expected = "\
+..........
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+......o.#.
+....oooo#.
+#########.
+";
+    assert_eq!(driver.simulation().render(), expected);
+}
@@ -0,0 +1,78 @@
+//! Traces of solving days, written as JSON lines.
+
+use std::fs;
+use std::time::Duration;
+
+use advent_of_code_2022::runner::{self, InputSource};
+use advent_of_code_2022::solution::Part;
+use advent_of_code_2022::trace::{self, Level};
+
+const DAY05_EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+/// The records of solving the examples of days 5, 11 and 14 at `level`.
+fn trace_examples(level: Level, parts: &[Part]) -> Vec<String> {
+    let file = std::env::temp_dir().join(format!("aoc-trace-{}-{}.jsonl", std::process::id(), level));
+    trace::to_file(level, &file).unwrap();
+    let (day05, days) = (runner::find_day(5).unwrap(), [11, 14].map(|day| runner::find_day(day).unwrap()));
+    let timeout = Some(Duration::from_secs(10));
+    let mut outcomes = runner::run(&[day05], parts, &InputSource::Inline(DAY05_EXAMPLE.to_string()), &[], timeout, |_| ());
+    outcomes.extend(runner::run(&days, parts, &InputSource::Example, &[], timeout, |_| ()));
+    trace::finish().unwrap();
+    assert!(outcomes.iter().all(|outcome| outcome.succeeded()));
+
+    let text = fs::read_to_string(&file).unwrap();
+    fs::remove_file(file).unwrap();
+    text.lines().map(str::to_string).collect()
+}
+
+fn count(records: &[String], part: &str) -> usize {
+    records.iter().filter(|record| record.contains(part)).count()
+}
+
+// Tracing is global, so the levels are tried one after the other.
+#[test]
+fn solving_is_traced_up_to_the_level() {
+    let records = trace_examples(Level::TRACE, &[Part::One]);
+    assert!(records[0].starts_with("{\"time_us\":"), "{}", records[0]);
+    assert!(records[0].ends_with(",\"level\":\"INFO\",\"kind\":\"enter\",\"span\":\"\",\"name\":\"day05\",\"fields\":{}}"), "{}", records[0]);
+    assert!(records[1].ends_with(",\"level\":\"DEBUG\",\"kind\":\"enter\",\"span\":\"day05\",\"name\":\"parse\",\"fields\":{}}"), "{}", records[1]);
+    assert!(records[2].contains(",\"kind\":\"exit\",\"span\":\"day05\",\"name\":\"parse\",\"fields\":{\"elapsed_us\":"), "{}", records[2]);
+    assert!(records[3].ends_with(",\"kind\":\"enter\",\"span\":\"day05\",\"name\":\"part1\",\"fields\":{}}"), "{}", records[3]);
+    assert!(records[4].ends_with(",\"level\":\"TRACE\",\"kind\":\"event\",\"span\":\"day05/part1\",\"name\":\"move\",\"fields\":{\"count\":1,\"from\":2,\"to\":1}}"), "{}", records[4]);
+    assert_eq!(count(&records, "\"name\":\"move\""), 4);
+    assert_eq!(count(&records, "\"name\":\"answer\",\"fields\":{\"part\":1,\"answer\":\"CMZ\"}"), 1);
+
+    assert_eq!(count(&records, "\"span\":\"day11/part1\",\"name\":\"throw\""), 101 + 95 + 7 + 105);
+    assert_eq!(count(&records, "\"name\":\"throw\",\"fields\":{\"round\":1,\"from\":0,\"to\":3,\"worry\":500}"), 1);
+    assert_eq!(count(&records, "\"name\":\"settle\""), 24);
+    assert_eq!(count(&records, "\"name\":\"settle\",\"fields\":{\"grain\":1,\"x\":500,\"y\":8}"), 1);
+    assert_eq!(count(&records, "\"kind\":\"exit\",\"span\":\"\""), 3);
+
+    let records = trace_examples(Level::INFO, &[Part::One]);
+    assert_eq!(records.len(), 3 * 3);
+    assert_eq!(count(&records, "\"level\":\"INFO\""), records.len());
+
+    let records = trace_examples(Level::DEBUG, &Part::ALL);
+    assert_eq!(count(&records, "\"level\":\"TRACE\""), 0);
+    assert_eq!(count(&records, "\"kind\":\"enter\",\"span\":\"day05\",\"name\":\"parse\""), 1);
+    assert_eq!(count(&records, "\"name\":\"parse\""), 3 * 2);
+    assert_eq!(count(&records, "\"name\":\"part1\""), 3 * 2);
+    assert_eq!(count(&records, "\"name\":\"part2\""), 3 * 2);
+}
+
+#[test]
+fn levels_are_parsed_by_name() {
+    assert_eq!(Level::parse("trace"), Some(Level::TRACE));
+    assert_eq!(Level::parse("DEBUG"), Some(Level::DEBUG));
+    assert_eq!(Level::parse("warn"), None);
+    assert!(Level::INFO < Level::TRACE);
+}
@@ -0,0 +1,63 @@
+//! Noticing changed input files and comparing answers with those of the previous run.
+
+use std::fs;
+use std::time::Duration;
+
+use advent_of_code_2022::error::Error;
+use advent_of_code_2022::runner::Outcome;
+use advent_of_code_2022::solution::{Answer, Part};
+use advent_of_code_2022::watch::{self, Watcher};
+
+fn outcome(part: Part, result: Result<Answer, Error>) -> Outcome {
+    Outcome { day: 10, part, input: "day10.txt".to_string(), elapsed: Duration::from_millis(2), result }
+}
+
+#[test]
+fn watcher_notices_changes() {
+    let directory = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let (input, example) = (directory.join("day01.txt"), directory.join("day01-test.txt"));
+    fs::write(&input, "1000\n").unwrap();
+
+    let mut watcher = Watcher::new(vec![input.clone(), example.clone()]);
+    assert!(watcher.changed().is_empty());
+    fs::write(&input, "1000\n2000\n").unwrap();
+    assert_eq!(watcher.changed(), vec![input.clone()]);
+    assert!(watcher.changed().is_empty());
+
+    fs::write(&example, "1\n").unwrap();
+    fs::remove_file(&input).unwrap();
+    assert_eq!(watcher.changed(), [input, example]);
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn answers_are_compared_with_the_previous_run() {
+    let first = || outcome(Part::One, Ok(Answer::Number(13140)));
+    assert_eq!(watch::describe(&first(), &[]), "Day 10 Part 1 (day10.txt): 13140 (2.00ms)");
+    assert_eq!(watch::describe(&first(), &[first()]), "Day 10 Part 1 (day10.txt): 13140 (2.00ms)  unchanged");
+
+    let second = outcome(Part::One, Ok(Answer::Number(13180)));
+    assert_eq!(watch::describe(&second, &[first()]), "Day 10 Part 1 (day10.txt): 13180 (2.00ms)  was 13140");
+    let failed = outcome(Part::One, Err(Error::no_answer("no signal")));
+    assert_eq!(watch::describe(&failed, &[second]), "Day 10 Part 1 (day10.txt): error: No answer: no signal (2.00ms)  was 13180");
+
+    let other_part = outcome(Part::Two, Ok(Answer::Number(1)));
+    assert_eq!(watch::describe(&other_part, &[first()]), "Day 10 Part 2 (day10.txt): 1 (2.00ms)");
+}
+
+#[test]
+fn changed_lines_of_text_answers_are_shown() {
+    let before = outcome(Part::Two, Ok(Answer::Text("##..\n#..#\n.##.".to_string())));
+    let after = outcome(Part::Two, Ok(Answer::Text("##..\n#.##\n.##.\n....".to_string())));
+    assert_eq!(watch::describe(&after, &[before]), "\
+Day 10 Part 2 (day10.txt): (2.00ms)  changed
+##..
+#.##
+.##.
+....
+Difference:
+- #..#
++ #.##
++ ....");
+}